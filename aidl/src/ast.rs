@@ -0,0 +1,124 @@
+#[derive(Clone, Debug)]
+pub enum Token {
+    Ident(String),
+    Int(String),
+    Float(String),
+    Str(String),
+    Char(String),
+    Punct(&'static str),
+}
+
+#[derive(Clone, Debug)]
+pub struct Expr {
+    pub tokens: Vec<Token>,
+    pub line: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Type {
+    pub name: Vec<String>,
+    pub generics: Vec<Type>,
+    pub dims: Vec<Option<String>>,
+    pub nullable: bool,
+    pub line: usize,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    In,
+    Out,
+    InOut,
+}
+
+#[derive(Clone, Debug)]
+pub struct Arg {
+    pub direction: Direction,
+    pub ty: Type,
+    pub name: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Method {
+    pub name: String,
+    pub oneway: bool,
+    pub ret: Option<Type>,
+    pub args: Vec<Arg>,
+    pub id: Option<u32>,
+    pub line: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub ty: Type,
+    pub name: String,
+    pub value: Expr,
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub ty: Type,
+    pub name: String,
+    pub default: Option<Expr>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Interface {
+    pub name: String,
+    pub oneway: bool,
+    pub methods: Vec<Method>,
+    pub constants: Vec<Constant>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Parcelable {
+    pub name: String,
+    pub fields: Option<Vec<Field>>,
+    pub constants: Vec<Constant>,
+    pub rust_type: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Enumerator {
+    pub name: String,
+    pub value: Option<Expr>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Enum {
+    pub name: String,
+    pub backing: Type,
+    pub enumerators: Vec<Enumerator>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Union {
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub constants: Vec<Constant>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Item {
+    Interface(Interface),
+    Parcelable(Parcelable),
+    Enum(Enum),
+    Union(Union),
+}
+
+impl Item {
+    pub fn name(&self) -> &str {
+        match self {
+            Item::Interface(i) => &i.name,
+            Item::Parcelable(p) => &p.name,
+            Item::Enum(e) => &e.name,
+            Item::Union(u) => &u.name,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Document {
+    pub package: Vec<String>,
+    pub imports: Vec<Vec<String>>,
+    pub items: Vec<Item>,
+}
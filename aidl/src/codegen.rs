@@ -0,0 +1,1092 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    ast::{Arg, Constant, Direction, Document, Enum, Expr, Field, Interface, Item, Method, Parcelable, Token, Type, Union},
+    parser::parse_int,
+};
+
+pub type CodegenError = (usize, String);
+
+type Result<T> = std::result::Result<T, CodegenError>;
+
+const UNEXPECTED_NULL: &str = ".ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Prim {
+    Bool,
+    Byte,
+    Char,
+    Int,
+    Long,
+    Float,
+    Double,
+}
+
+impl Prim {
+    fn from_name(name: &str) -> Option<Prim> {
+        Some(match name {
+            "boolean" => Prim::Bool,
+            "byte" => Prim::Byte,
+            "char" => Prim::Char,
+            "int" => Prim::Int,
+            "long" => Prim::Long,
+            "float" => Prim::Float,
+            "double" => Prim::Double,
+            _ => return None,
+        })
+    }
+
+    fn rust(self) -> &'static str {
+        match self {
+            Prim::Bool => "bool",
+            Prim::Byte => "i8",
+            Prim::Char => "u16",
+            Prim::Int => "i32",
+            Prim::Long => "i64",
+            Prim::Float => "f32",
+            Prim::Double => "f64",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Interface,
+    Parcelable(Option<String>, bool),
+    Enum,
+    Union,
+}
+
+#[derive(Clone, Debug)]
+enum Ty {
+    Prim(Prim),
+    String,
+    Binder,
+    Fd,
+    Parcelable(String),
+    Enum(String),
+    Interface(String),
+    Array(Box<Ty>),
+}
+
+#[derive(Clone, Debug)]
+enum Scope {
+    Item,
+    Enum,
+}
+
+pub struct Generator<'a> {
+    items: BTreeMap<Vec<String>, Kind>,
+    documents: &'a [Document],
+}
+
+struct Module<'a> {
+    generator: &'a Generator<'a>,
+    document: &'a Document,
+}
+
+fn snake_case(name: &str) -> String {
+    let mut ret = String::new();
+    let chars = name.chars().collect::<Vec<_>>();
+    for (idx, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev_lower = idx > 0 && (chars[idx - 1].is_ascii_lowercase() || chars[idx - 1].is_ascii_digit());
+            let next_lower = chars.get(idx + 1).is_some_and(|c| c.is_ascii_lowercase());
+            if idx > 0 && chars[idx - 1] != '_' && (prev_lower || next_lower) {
+                ret.push('_');
+            }
+            ret.push(c.to_ascii_lowercase());
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+fn ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for", "if",
+        "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct", "trait", "true",
+        "type", "unsafe", "use", "where", "while", "yield", "abstract", "become", "do", "final", "macro", "override", "priv",
+        "try", "typeof", "unsized", "virtual",
+    ];
+
+    if matches!(name, "self" | "Self" | "super" | "crate") {
+        format!("{}_", name)
+    } else if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn snake_ident(name: &str) -> String {
+    ident(&snake_case(name))
+}
+
+impl<'a> Generator<'a> {
+    pub fn new(documents: &'a [Document]) -> Generator<'a> {
+        let mut items = BTreeMap::new();
+        for document in documents {
+            for item in &document.items {
+                let mut name = document.package.clone();
+                name.push(item.name().to_string());
+
+                let kind = match item {
+                    Item::Interface(_) => Kind::Interface,
+                    Item::Parcelable(p) => Kind::Parcelable(p.rust_type.clone(), p.fields.is_some()),
+                    Item::Enum(_) => Kind::Enum,
+                    Item::Union(_) => Kind::Union,
+                };
+
+                items.insert(name, kind);
+            }
+        }
+
+        Generator { items, documents }
+    }
+
+    pub fn generate(&self) -> std::result::Result<String, (usize, usize, String)> {
+        let mut modules: BTreeMap<Vec<String>, String> = BTreeMap::new();
+
+        for (idx, document) in self.documents.iter().enumerate() {
+            let module = Module {
+                generator: self,
+                document,
+            };
+
+            let code = modules.entry(document.package.clone()).or_default();
+            for item in &document.items {
+                module.item(item, code).map_err(|(line, message)| (idx, line, message))?;
+            }
+        }
+
+        let mut out = String::new();
+        render_modules(&modules, &[], &mut out);
+        Ok(out)
+    }
+}
+
+fn render_modules(modules: &BTreeMap<Vec<String>, String>, prefix: &[String], out: &mut String) {
+    if let Some(code) = modules.get(prefix) {
+        out.push_str(code);
+    }
+
+    let mut children = modules
+        .keys()
+        .filter(|k| k.len() > prefix.len() && k.starts_with(prefix))
+        .map(|k| k[prefix.len()].clone())
+        .collect::<Vec<_>>();
+    children.dedup();
+
+    for child in children {
+        let mut path = prefix.to_vec();
+        path.push(child.clone());
+
+        writeln!(out, "pub mod {} {{", ident(&child)).unwrap();
+        if prefix.is_empty() {
+            writeln!(
+                out,
+                "#![allow(non_upper_case_globals, non_snake_case, clippy::all, unused_imports, unused_mut, unused_variables)]"
+            )
+            .unwrap();
+        }
+        render_modules(modules, &path, out);
+        writeln!(out, "}}").unwrap();
+    }
+}
+
+impl<'a> Module<'a> {
+    fn path_of(&self, name: &[String]) -> String {
+        let package = &self.document.package;
+        if name.len() == package.len() + 1 && name.starts_with(package) {
+            return ident(name.last().unwrap());
+        }
+
+        let mut path = "super::".repeat(package.len());
+        path.push_str(&name.iter().map(|s| ident(s)).collect::<Vec<_>>().join("::"));
+        path
+    }
+
+    fn lookup(&self, name: &[String]) -> Option<(Vec<String>, Kind)> {
+        let candidates = if name.len() == 1 {
+            let mut candidates = self
+                .document
+                .imports
+                .iter()
+                .filter(|i| i.last() == name.last())
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let mut local = self.document.package.clone();
+            local.push(name[0].clone());
+            candidates.push(local);
+            candidates
+        } else {
+            vec![name.to_vec()]
+        };
+
+        candidates
+            .into_iter()
+            .find_map(|c| self.generator.items.get(&c).map(|kind| (c.clone(), kind.clone())))
+    }
+
+    fn resolve(&self, ty: &Type) -> Result<Ty> {
+        let err = |message: String| Err((ty.line, message));
+
+        let base = if ty.name.len() == 1 && ty.name[0] == "List" {
+            match &ty.generics[..] {
+                [element] => {
+                    if element.nullable {
+                        return err("nullable list elements are not supported".to_string());
+                    }
+
+                    Ty::Array(Box::new(self.resolve(element)?))
+                }
+                _ => return err("List requires exactly one type parameter".to_string()),
+            }
+        } else if let Some(prim) = ty
+            .name
+            .first()
+            .filter(|_| ty.name.len() == 1)
+            .and_then(|n| Prim::from_name(n))
+        {
+            Ty::Prim(prim)
+        } else if ty.name.len() == 1 && ty.name[0] == "String" {
+            Ty::String
+        } else if ty.name.len() == 1 && ty.name[0] == "IBinder" {
+            Ty::Binder
+        } else if ty.name.len() == 1 && ty.name[0] == "ParcelFileDescriptor" {
+            Ty::Fd
+        } else if ty.name.len() == 1 && ty.name[0] == "ParcelableHolder" {
+            Ty::Parcelable("::ndkbinder::ParcelableHolder".to_string())
+        } else {
+            match self.lookup(&ty.name) {
+                Some((name, Kind::Interface)) => Ty::Interface(self.path_of(&name)),
+                Some((_, Kind::Parcelable(Some(rust_type), _))) => Ty::Parcelable(rust_type),
+                Some((name, Kind::Parcelable(None, true))) => Ty::Parcelable(self.path_of(&name)),
+                Some((name, Kind::Parcelable(None, false))) => {
+                    return err(format!(
+                        "unstructured parcelable '{}' requires a rust_type declaration",
+                        name.join(".")
+                    ))
+                }
+                Some((name, Kind::Union)) => Ty::Parcelable(self.path_of(&name)),
+                Some((name, Kind::Enum)) => Ty::Enum(self.path_of(&name)),
+                None => return err(format!("unknown type '{}'", ty.name.join("."))),
+            }
+        };
+
+        if ty.dims.iter().any(|d| d.is_some()) {
+            return err("fixed-size arrays are not supported".to_string());
+        }
+
+        let ret = ty.dims.iter().fold(base, |ty, _| Ty::Array(Box::new(ty)));
+        if let Ty::Array(element) = &ret {
+            if matches!(**element, Ty::Array(_)) {
+                return err("nested arrays are not supported".to_string());
+            }
+        }
+
+        Ok(ret)
+    }
+
+    fn owned(&self, ty: &Ty, nullable: bool) -> String {
+        let base = match ty {
+            Ty::Prim(prim) => return prim.rust().to_string(),
+            Ty::Enum(path) => return path.clone(),
+            Ty::String => "String".to_string(),
+            Ty::Binder => "::ndkbinder::IBinder".to_string(),
+            Ty::Interface(path) => format!("::ndkbinder::Strong<dyn {}>", path),
+            Ty::Fd => "::std::os::fd::OwnedFd".to_string(),
+            Ty::Parcelable(path) => path.clone(),
+            Ty::Array(element) => format!("Vec<{}>", self.owned(element, nullable)),
+        };
+
+        if nullable {
+            format!("Option<{}>", base)
+        } else {
+            base
+        }
+    }
+
+    fn borrowed(&self, ty: &Ty, nullable: bool) -> String {
+        let base = match ty {
+            Ty::Prim(_) | Ty::Enum(_) => return self.owned(ty, nullable),
+            Ty::String => "&str".to_string(),
+            Ty::Binder => "&::ndkbinder::IBinder".to_string(),
+            Ty::Interface(path) => format!("&::ndkbinder::Strong<dyn {}>", path),
+            Ty::Fd => "::std::os::fd::BorrowedFd<'_>".to_string(),
+            Ty::Parcelable(path) => format!("&{}", path),
+            Ty::Array(element) => format!("&[{}]", self.owned(element, nullable)),
+        };
+
+        if nullable {
+            format!("Option<{}>", base)
+        } else {
+            base
+        }
+    }
+
+    fn borrow(&self, ty: &Ty, nullable: bool, value: &str) -> String {
+        match (ty, nullable) {
+            (Ty::Prim(_) | Ty::Enum(_), _) => value.to_string(),
+            (Ty::String, false) => format!("{}.as_str()", value),
+            (Ty::Array(_), false) => format!("{}.as_slice()", value),
+            (Ty::String | Ty::Array(_), true) => format!("{}.as_deref()", value),
+            (Ty::Fd, false) => format!("::std::os::fd::AsFd::as_fd(&{})", value),
+            (Ty::Fd, true) => format!("{}.as_ref().map(::std::os::fd::AsFd::as_fd)", value),
+            (_, false) => format!("&{}", value),
+            (_, true) => format!("{}.as_ref()", value),
+        }
+    }
+
+    fn read(&self, ty: &Ty, nullable: bool, parcel: &str) -> String {
+        format!("{}.read::<{}>()?", parcel, self.owned(ty, nullable))
+    }
+
+    fn write(&self, ty: &Ty, nullable: bool, parcel: &str, value: &str) -> String {
+        match (ty, nullable) {
+            (Ty::Parcelable(_), false) => format!("{}.write({})?;", parcel, value),
+            (Ty::Parcelable(_), true) => format!(
+                "match {} {{ Some(v) => {}.write(v)?, None => {}.write(&0i32)? }}",
+                value, parcel, parcel
+            ),
+            _ => format!("{}.write(&{})?;", parcel, value),
+        }
+    }
+
+    fn expr(&self, expr: &Expr, prim: Option<Prim>, scope: &Scope) -> Result<String> {
+        let mut out = String::new();
+        let mut tokens = expr.tokens.iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            let piece = match token {
+                Token::Int(s) => {
+                    let value = parse_int(s).ok_or((expr.line, format!("invalid integer '{}'", s)))?;
+                    match prim.unwrap_or(Prim::Int) {
+                        Prim::Byte if value > i8::MAX as i128 => format!("({}u8 as i8)", value),
+                        Prim::Int if value > i32::MAX as i128 => format!("({}u32 as i32)", value),
+                        Prim::Long if value > i64::MAX as i128 => format!("({}u64 as i64)", value),
+                        Prim::Float | Prim::Double => format!("{}.0", value),
+                        prim => format!("{}{}", value, prim.rust()),
+                    }
+                }
+                Token::Float(s) => {
+                    let s = s.trim_end_matches(['f', 'F', 'd', 'D']);
+                    if s.contains('.') || s.contains('e') {
+                        s.to_string()
+                    } else {
+                        format!("{}.0", s)
+                    }
+                }
+                Token::Str(s) => format!("\"{}\"", s),
+                Token::Char(c) => format!("('{}' as u16)", c),
+                Token::Ident(s) if s == "true" || s == "false" => s.clone(),
+                Token::Ident(s) => {
+                    let mut name = vec![s.clone()];
+                    while matches!(tokens.peek(), Some(Token::Punct("."))) {
+                        tokens.next();
+                        match tokens.next() {
+                            Some(Token::Ident(s)) => name.push(s.clone()),
+                            _ => return Err((expr.line, "expected identifier after '.'".to_string())),
+                        }
+                    }
+
+                    let member = name.pop().unwrap();
+                    if name.is_empty() {
+                        match scope {
+                            Scope::Enum => format!("Self::{}.0", ident(&member)),
+                            Scope::Item => format!("Self::{}", ident(&member)),
+                        }
+                    } else {
+                        match self.lookup(&name) {
+                            Some((name, _)) => format!("{}::{}", self.path_of(&name), ident(&member)),
+                            None => return Err((expr.line, format!("unknown type '{}'", name.join(".")))),
+                        }
+                    }
+                }
+                Token::Punct("~") => "!".to_string(),
+                Token::Punct("{") => "vec![".to_string(),
+                Token::Punct("}") => "]".to_string(),
+                Token::Punct(p) => p.to_string(),
+            };
+
+            if out.ends_with(|c: char| c.is_alphanumeric() || c == '_') && piece.starts_with(|c: char| c.is_alphanumeric()) {
+                out.push(' ');
+            }
+            out.push_str(&piece);
+        }
+
+        Ok(out)
+    }
+
+    fn default_value(&self, field: &Field, ty: &Ty) -> Result<String> {
+        let expr = match &field.default {
+            None => return Ok("Default::default()".to_string()),
+            Some(expr) => expr,
+        };
+
+        let nullable = field.ty.nullable;
+        let value = match ty {
+            Ty::Prim(prim) => self.expr(expr, Some(*prim), &Scope::Item)?,
+            Ty::Enum(_) => self.expr(expr, None, &Scope::Item)?,
+            Ty::String => format!("({}).to_string()", self.expr(expr, None, &Scope::Item)?),
+            Ty::Array(element) => match &**element {
+                Ty::Prim(prim) => self.expr(expr, Some(*prim), &Scope::Item)?,
+                Ty::Enum(_) => self.expr(expr, None, &Scope::Item)?,
+                _ => return Err((expr.line, format!("unsupported default value for '{}'", field.name))),
+            },
+            _ => return Err((expr.line, format!("unsupported default value for '{}'", field.name))),
+        };
+
+        if nullable && !matches!(ty, Ty::Prim(_) | Ty::Enum(_)) {
+            Ok(format!("Some({})", value))
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn constants(&self, constants: &[Constant], out: &mut String) -> Result<()> {
+        for constant in constants {
+            let ty = self.resolve(&constant.ty)?;
+            let (rust_ty, prim) = match &ty {
+                Ty::Prim(prim) => (prim.rust().to_string(), Some(*prim)),
+                Ty::String => ("&'static str".to_string(), None),
+                _ => return Err((constant.ty.line, format!("unsupported constant type for '{}'", constant.name))),
+            };
+
+            writeln!(
+                out,
+                "pub const {}: {} = {};",
+                ident(&constant.name),
+                rust_ty,
+                self.expr(&constant.value, prim, &Scope::Item)?
+            )
+            .unwrap();
+        }
+
+        Ok(())
+    }
+
+    fn item(&self, item: &Item, out: &mut String) -> Result<()> {
+        match item {
+            Item::Interface(interface) => self.interface(interface, out),
+            Item::Parcelable(parcelable) => self.parcelable(parcelable, out),
+            Item::Enum(enumeration) => self.enumeration(enumeration, out),
+            Item::Union(union) => self.union(union, out),
+        }
+    }
+
+    fn fields(&self, fields: &[Field]) -> Result<Vec<(String, Ty, bool)>> {
+        fields
+            .iter()
+            .map(|f| Ok((snake_ident(&f.name), self.resolve(&f.ty)?, f.ty.nullable)))
+            .collect()
+    }
+
+    fn parcelable(&self, parcelable: &Parcelable, out: &mut String) -> Result<()> {
+        let fields = match &parcelable.fields {
+            Some(fields) => fields,
+            None => return Ok(()),
+        };
+
+        let name = ident(&parcelable.name);
+        let resolved = self.fields(fields)?;
+
+        for (field, (_, ty, nullable)) in fields.iter().zip(&resolved) {
+            if !nullable && matches!(ty, Ty::Binder | Ty::Interface(_) | Ty::Fd) {
+                return Err((field.ty.line, format!("field '{}' must be @nullable", field.name)));
+            }
+        }
+
+        writeln!(out, "#[derive(Debug)]").unwrap();
+        writeln!(out, "pub struct {} {{", name).unwrap();
+        for (field_name, ty, nullable) in &resolved {
+            writeln!(out, "pub {}: {},", field_name, self.owned(ty, *nullable)).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "impl {} {{", name).unwrap();
+        self.constants(&parcelable.constants, out)?;
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "impl Default for {} {{", name).unwrap();
+        writeln!(out, "fn default() -> Self {{").unwrap();
+        writeln!(out, "Self {{").unwrap();
+        for (field, (field_name, ty, _)) in fields.iter().zip(&resolved) {
+            writeln!(out, "{}: {},", field_name, self.default_value(field, ty)?).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        writeln!(out, "}}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        let mut reads = String::new();
+        let mut writes = String::new();
+        for (field_name, ty, nullable) in &resolved {
+            writeln!(
+                reads,
+                "if reader.has_more_data() {{ value.{} = {}; }}",
+                field_name,
+                self.read(ty, *nullable, "parcel")
+            )
+            .unwrap();
+            writeln!(
+                writes,
+                "{}",
+                self.write(
+                    ty,
+                    *nullable,
+                    "parcel",
+                    &self.borrow(ty, *nullable, &format!("self.{}", field_name))
+                )
+            )
+            .unwrap();
+        }
+
+        self.nullable_parcelable_impls(&name, &parcelable.name, out);
+
+        writeln!(
+            out,
+            r#"impl ::ndkbinder::ReadNullable for {name} {{
+    fn read_nullable(parcel: &::ndkbinder::Parcel) -> Result<Option<Self>, ::ndkbinder::Status> {{
+        if parcel.read::<i32>()? == 0 {{
+            return Ok(None);
+        }}
+
+        parcel.read_sized(|reader| {{
+            let mut value = {name}::default();
+            {reads}
+            Ok(Some(value))
+        }})
+    }}
+}}
+
+impl ::ndkbinder::Write for {name} {{
+    fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> Result<(), ::ndkbinder::Status> {{
+        parcel.write(&1i32)?;
+        parcel.write_sized(|parcel| {{
+            {writes}
+            Ok(())
+        }})
+    }}
+}}"#
+        )
+        .unwrap();
+
+        Ok(())
+    }
+
+    fn nullable_parcelable_impls(&self, name: &str, aidl_name: &str, out: &mut String) {
+        let descriptor = self.descriptor(aidl_name);
+
+        writeln!(
+            out,
+            r#"impl ::ndkbinder::ParcelableMetadata for {name} {{
+    const DESCRIPTOR: &'static str = "{descriptor}";
+}}
+
+impl ::ndkbinder::Read for {name} {{
+    fn read(parcel: &::ndkbinder::Parcel) -> Result<Self, ::ndkbinder::Status> {{
+        <Self as ::ndkbinder::ReadNullable>::read_nullable(parcel)?{UNEXPECTED_NULL}
+    }}
+}}
+
+impl ::ndkbinder::WriteNullable for {name} {{}}
+impl ::ndkbinder::ReadArray for {name} {{}}
+impl ::ndkbinder::WriteArray for {name} {{}}"#
+        )
+        .unwrap();
+    }
+
+    fn enumeration(&self, enumeration: &Enum, out: &mut String) -> Result<()> {
+        let name = ident(&enumeration.name);
+        let prim = match Prim::from_name(&enumeration.backing.name[0]) {
+            Some(prim @ (Prim::Byte | Prim::Int | Prim::Long)) => prim,
+            _ => {
+                return Err((
+                    enumeration.backing.line,
+                    "enum backing type must be byte, int or long".to_string(),
+                ))
+            }
+        };
+
+        writeln!(
+            out,
+            "#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]"
+        )
+        .unwrap();
+        writeln!(out, "pub struct {}(pub {});", name, prim.rust()).unwrap();
+
+        writeln!(out, "impl {} {{", name).unwrap();
+        let mut previous: Option<String> = None;
+        for enumerator in &enumeration.enumerators {
+            let value = match (&enumerator.value, &previous) {
+                (Some(expr), _) => self.expr(expr, Some(prim), &Scope::Enum)?,
+                (None, Some(previous)) => format!("Self::{}.0 + 1", previous),
+                (None, None) => format!("0{}", prim.rust()),
+            };
+
+            writeln!(out, "pub const {}: {} = {}({});", ident(&enumerator.name), name, name, value).unwrap();
+            previous = Some(ident(&enumerator.name));
+        }
+        writeln!(out, "}}").unwrap();
+
+        writeln!(
+            out,
+            r#"impl ::ndkbinder::Read for {name} {{
+    fn read(parcel: &::ndkbinder::Parcel) -> Result<Self, ::ndkbinder::Status> {{
+        Ok({name}(parcel.read()?))
+    }}
+}}
+
+impl ::ndkbinder::Write for {name} {{
+    fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> Result<(), ::ndkbinder::Status> {{
+        parcel.write(&self.0)
+    }}
+}}
+
+impl ::ndkbinder::ReadArray for {name} {{
+    fn read_array(parcel: &::ndkbinder::Parcel) -> Result<Option<Vec<Self>>, ::ndkbinder::Status> {{
+        Ok(parcel.read::<Option<Vec<{backing}>>>()?.map(|v| v.into_iter().map({name}).collect()))
+    }}
+}}
+
+impl ::ndkbinder::WriteArray for {name} {{
+    fn write_array(parcel: &mut ::ndkbinder::Parcel, values: Option<&[Self]>) -> Result<(), ::ndkbinder::Status> {{
+        parcel.write(&values.map(|v| v.iter().map(|e| e.0).collect::<Vec<_>>()))
+    }}
+}}"#,
+            backing = prim.rust()
+        )
+        .unwrap();
+
+        Ok(())
+    }
+
+    fn union(&self, union: &Union, out: &mut String) -> Result<()> {
+        let name = ident(&union.name);
+        let resolved = self.fields(&union.fields)?;
+        let first = match union.fields.first() {
+            Some(first) => first,
+            None => return Err((0, format!("union '{}' must have at least one field", union.name))),
+        };
+
+        let variant = |field: &Field| ident(&field.name[..1].to_ascii_uppercase()) + &field.name[1..];
+
+        writeln!(out, "#[derive(Debug)]").unwrap();
+        writeln!(out, "pub enum {} {{", name).unwrap();
+        for (field, (_, ty, nullable)) in union.fields.iter().zip(&resolved) {
+            writeln!(out, "{}({}),", variant(field), self.owned(ty, *nullable)).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "impl {} {{", name).unwrap();
+        self.constants(&union.constants, out)?;
+        for (idx, field) in union.fields.iter().enumerate() {
+            writeln!(
+                out,
+                "pub const TAG_{}: i32 = {};",
+                snake_case(&field.name).to_uppercase(),
+                idx
+            )
+            .unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        writeln!(
+            out,
+            "impl Default for {} {{ fn default() -> Self {{ Self::{}({}) }} }}",
+            name,
+            variant(first),
+            self.default_value(first, &resolved[0].1)?
+        )
+        .unwrap();
+
+        let mut reads = String::new();
+        let mut writes = String::new();
+        for (idx, (field, (_, ty, nullable))) in union.fields.iter().zip(&resolved).enumerate() {
+            writeln!(
+                reads,
+                "{} => {}::{}({}),",
+                idx,
+                name,
+                variant(field),
+                self.read(ty, *nullable, "parcel")
+            )
+            .unwrap();
+            writeln!(
+                writes,
+                "Self::{}(v) => {{ parcel.write(&{}i32)?; {} }}",
+                variant(field),
+                idx,
+                self.write(ty, *nullable, "parcel", &self.borrow(ty, *nullable, "(*v)"))
+            )
+            .unwrap();
+        }
+
+        self.nullable_parcelable_impls(&name, &union.name, out);
+
+        writeln!(
+            out,
+            r#"impl ::ndkbinder::ReadNullable for {name} {{
+    fn read_nullable(parcel: &::ndkbinder::Parcel) -> Result<Option<Self>, ::ndkbinder::Status> {{
+        if parcel.read::<i32>()? == 0 {{
+            return Ok(None);
+        }}
+
+        Ok(Some(match parcel.read::<i32>()? {{
+            {reads}
+            _ => return Err(::ndkbinder::Status::bad_value()),
+        }}))
+    }}
+}}
+
+impl ::ndkbinder::Write for {name} {{
+    fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> Result<(), ::ndkbinder::Status> {{
+        parcel.write(&1i32)?;
+
+        match self {{
+            {writes}
+        }}
+
+        Ok(())
+    }}
+}}"#
+        )
+        .unwrap();
+
+        Ok(())
+    }
+
+    fn signature(&self, method: &Method) -> Result<String> {
+        let mut args = vec!["&self".to_string()];
+        for arg in &method.args {
+            let ty = self.resolve(&arg.ty)?;
+            let rust_ty = match arg.direction {
+                Direction::In => self.borrowed(&ty, arg.ty.nullable),
+                Direction::Out | Direction::InOut => format!("&mut {}", self.owned(&ty, arg.ty.nullable)),
+            };
+            args.push(format!("{}: {}", snake_ident(&arg.name), rust_ty));
+        }
+
+        let ret = match &method.ret {
+            None => "()".to_string(),
+            Some(ret) => self.owned(&self.resolve(ret)?, ret.nullable),
+        };
+
+        Ok(format!(
+            "fn {}({}) -> Result<{}, ::ndkbinder::Status>",
+            snake_ident(&method.name),
+            args.join(", "),
+            ret
+        ))
+    }
+
+    fn transaction_name(method: &Method) -> String {
+        format!("TRANSACTION_{}", snake_case(&method.name).to_uppercase())
+    }
+
+    fn descriptor(&self, name: &str) -> String {
+        self.document
+            .package
+            .iter()
+            .map(String::as_str)
+            .chain([name])
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn interface(&self, interface: &Interface, out: &mut String) -> Result<()> {
+        let name = ident(&interface.name);
+        let descriptor = self.descriptor(&interface.name);
+
+        let has_ids = interface.methods.iter().filter(|m| m.id.is_some()).count();
+        if has_ids != 0 && has_ids != interface.methods.len() {
+            return Err((
+                interface.methods[0].line,
+                "either all methods or none must have explicit transaction ids".to_string(),
+            ));
+        }
+
+        let methods = interface
+            .methods
+            .iter()
+            .map(|m| Method {
+                oneway: m.oneway || interface.oneway,
+                ..m.clone()
+            })
+            .collect::<Vec<_>>();
+
+        for method in &methods {
+            if method.oneway && (method.ret.is_some() || method.args.iter().any(|a| a.direction != Direction::In)) {
+                return Err((method.line, format!("oneway method '{}' cannot return values", method.name)));
+            }
+        }
+
+        writeln!(out, "pub trait {}: Send + Sync {{", name).unwrap();
+        for method in &methods {
+            writeln!(out, "{};", self.signature(method)?).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        if !interface.constants.is_empty() {
+            writeln!(out, "impl dyn {} {{", name).unwrap();
+            self.constants(&interface.constants, out)?;
+            writeln!(out, "}}").unwrap();
+        }
+
+        let stub = format!("{}Stub", interface.name);
+        let proxy = format!("{}Proxy", interface.name);
+
+        writeln!(out, "pub struct {}(Box<dyn {}>);", stub, name).unwrap();
+        writeln!(out, "impl {} {{", stub).unwrap();
+        for (idx, method) in methods.iter().enumerate() {
+            writeln!(
+                out,
+                "pub const {}: u32 = ::ndkbinder::FIRST_CALL_TRANSACTION + {};",
+                Self::transaction_name(method),
+                method.id.unwrap_or(idx as u32)
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "pub fn new_binder<T: {} + 'static>(service: T) -> ::ndkbinder::IBinder {{ {}(Box::new(service)).into() }}",
+            name, stub
+        )
+        .unwrap();
+        writeln!(out, "}}").unwrap();
+
+        let mut arms = String::new();
+        for method in &methods {
+            arms.push_str(&self.stub_arm(method, &stub)?);
+        }
+
+        writeln!(
+            out,
+            r#"impl ::ndkbinder::Class for {stub} {{
+    const INTERFACE_NAME: &'static str = "{descriptor}";
+
+    fn enable_status_header() -> bool {{
+        true
+    }}
+
+    fn on_transact(&self, code: u32, data: &::ndkbinder::Parcel, reply: Option<&mut ::ndkbinder::Parcel>) -> Result<(), ::ndkbinder::Status> {{
+        match code {{
+            {arms}
+            _ => Err(::ndkbinder::Status::unknown_transaction()),
+        }}
+    }}
+}}
+
+::ndkbinder::define_class!({stub});
+
+::ndkbinder::define_interface!(dyn {name}, {stub});
+
+pub struct {proxy} {{
+    binder: ::ndkbinder::Strong<dyn {name}>,
+}}
+
+impl {proxy} {{
+    pub fn new(binder: ::ndkbinder::Strong<dyn {name}>) -> Self {{
+        {proxy} {{ binder }}
+    }}
+
+    pub fn as_binder(&self) -> &::ndkbinder::IBinder {{
+        self.binder.as_binder()
+    }}
+}}"#
+        )
+        .unwrap();
+
+        writeln!(out, "impl {} for {} {{", name, proxy).unwrap();
+        for method in &methods {
+            out.push_str(&self.proxy_method(method, &stub)?);
+        }
+        writeln!(out, "}}").unwrap();
+
+        Ok(())
+    }
+
+    fn stub_arm(&self, method: &Method, stub: &str) -> Result<String> {
+        let mut body = String::new();
+        let mut call_args = Vec::new();
+        let mut outs = Vec::new();
+
+        for arg in &method.args {
+            let ty = self.resolve(&arg.ty)?;
+            let nullable = arg.ty.nullable;
+            let var = format!("_arg_{}", snake_case(&arg.name));
+
+            match arg.direction {
+                Direction::In => {
+                    writeln!(body, "let {} = {};", var, self.read(&ty, nullable, "data")).unwrap();
+                    call_args.push(self.borrow(&ty, nullable, &var));
+                }
+                Direction::InOut => {
+                    writeln!(body, "let mut {} = {};", var, self.read(&ty, nullable, "data")).unwrap();
+                    call_args.push(format!("&mut {}", var));
+                    outs.push((var, ty, nullable));
+                }
+                Direction::Out => {
+                    let init = match &ty {
+                        Ty::Array(_) if nullable => {
+                            "data.read_out_array_len()?.map(|len| (0..len).map(|_| Default::default()).collect())"
+                        }
+                        Ty::Array(_) => "(0..data.read_out_array_len()?.unwrap_or(0)).map(|_| Default::default()).collect()",
+                        _ => "Default::default()",
+                    };
+                    writeln!(body, "let mut {}: {} = {};", var, self.owned(&ty, nullable), init).unwrap();
+                    call_args.push(format!("&mut {}", var));
+                    outs.push((var, ty, nullable));
+                }
+            }
+        }
+
+        let call = format!("self.0.{}({})", snake_ident(&method.name), call_args.join(", "));
+
+        if method.oneway {
+            writeln!(body, "let _ = {};", call).unwrap();
+            writeln!(body, "Ok(())").unwrap();
+        } else {
+            writeln!(body, "let _aidl_reply = reply{}?;", UNEXPECTED_NULL).unwrap();
+            writeln!(body, "let _aidl_return = {}?;", call).unwrap();
+            if let Some(ret) = &method.ret {
+                let ty = self.resolve(ret)?;
+                writeln!(
+                    body,
+                    "{}",
+                    self.write(
+                        &ty,
+                        ret.nullable,
+                        "_aidl_reply",
+                        &self.borrow(&ty, ret.nullable, "_aidl_return")
+                    )
+                )
+                .unwrap();
+            }
+            for (var, ty, nullable) in &outs {
+                writeln!(
+                    body,
+                    "{}",
+                    self.write(ty, *nullable, "_aidl_reply", &self.borrow(ty, *nullable, var))
+                )
+                .unwrap();
+            }
+            writeln!(body, "Ok(())").unwrap();
+        }
+
+        Ok(format!("{}::{} => {{\n{}}}\n", stub, Self::transaction_name(method), body))
+    }
+
+    fn proxy_method(&self, method: &Method, stub: &str) -> Result<String> {
+        let mut writes = String::new();
+        let mut reads = String::new();
+        let mut outs = Vec::new();
+
+        for Arg { direction, ty, name } in &method.args {
+            let resolved = self.resolve(ty)?;
+            let var = snake_ident(name);
+
+            match direction {
+                Direction::In => writeln!(writes, "{}", self.write(&resolved, ty.nullable, "data", &var)).unwrap(),
+                Direction::InOut => {
+                    writeln!(
+                        writes,
+                        "{}",
+                        self.write(
+                            &resolved,
+                            ty.nullable,
+                            "data",
+                            &self.borrow(&resolved, ty.nullable, &format!("(*{})", var))
+                        )
+                    )
+                    .unwrap();
+                }
+                Direction::Out => match (&resolved, ty.nullable) {
+                    (Ty::Array(_), false) => writeln!(writes, "data.write(&({}.len() as i32))?;", var).unwrap(),
+                    (Ty::Array(_), true) => {
+                        writeln!(writes, "data.write(&{}.as_ref().map_or(-1, |v| v.len() as i32))?;", var).unwrap()
+                    }
+                    _ => {}
+                },
+            }
+
+            if *direction != Direction::In {
+                writeln!(
+                    reads,
+                    "let _out_{} = {};",
+                    snake_case(name),
+                    self.read(&resolved, ty.nullable, "reply")
+                )
+                .unwrap();
+                outs.push((var, format!("_out_{}", snake_case(name))));
+            }
+        }
+
+        let (flags, reply) = if method.oneway {
+            ("::ndkbinder::Flags::ONEWAY", "|_| Ok(())".to_string())
+        } else {
+            let ret = match &method.ret {
+                None => "()".to_string(),
+                Some(ret) => self.read(&self.resolve(ret)?, ret.nullable, "reply"),
+            };
+
+            (
+                "::ndkbinder::Flags::empty()",
+                format!(
+                    "|reply| {{
+                        let reply = reply{UNEXPECTED_NULL}?;
+                        let _aidl_return = {ret};
+                        {reads}
+                        Ok((_aidl_return, {outs}))
+                    }}",
+                    outs = outs.iter().map(|(_, out)| format!("{}, ", out)).collect::<String>(),
+                ),
+            )
+        };
+
+        let assigns = outs
+            .iter()
+            .map(|(var, out)| format!("*{} = {};\n", var, out))
+            .collect::<String>();
+
+        let (pattern, ret) = if method.oneway {
+            ("()".to_string(), "()")
+        } else {
+            (
+                format!(
+                    "(_aidl_return, {})",
+                    outs.iter().map(|(_, out)| format!("{}, ", out)).collect::<String>()
+                ),
+                "_aidl_return",
+            )
+        };
+
+        Ok(format!(
+            "{} {{
+                let {} = self.binder.as_binder().transact_with_status_header(
+                    {}::{},
+                    |data| {{
+                        {}
+                        Ok(())
+                    }},
+                    {},
+                    {},
+                )?;
+                {}
+                Ok({})
+            }}\n",
+            self.signature(method)?,
+            pattern,
+            stub,
+            Self::transaction_name(method),
+            writes,
+            reply,
+            flags,
+            assigns,
+            ret
+        ))
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    error::Error as StdError,
+    fmt::{Debug, Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+mod ast;
+mod codegen;
+mod parser;
+
+#[cfg(test)]
+mod test;
+
+pub enum Error {
+    Io(PathBuf, std::io::Error),
+    Syntax { file: PathBuf, line: usize, message: String },
+    Semantic { file: PathBuf, line: usize, message: String },
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(path, err) => f.write_fmt(format_args!("io error on {:?}: {:?}", path, err)),
+            Error::Syntax { file, line, message } => {
+                f.write_fmt(format_args!("syntax error at {:?}:{}: {}", file, line, message))
+            }
+            Error::Semantic { file, line, message } => {
+                f.write_fmt(format_args!("semantic error at {:?}:{}: {}", file, line, message))
+            }
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(path, err) => f.write_fmt(format_args!("{}: {}", path.display(), err)),
+            Error::Syntax { file, line, message } => f.write_fmt(format_args!("{}:{}: {}", file.display(), line, message)),
+            Error::Semantic { file, line, message } => f.write_fmt(format_args!("{}:{}: {}", file.display(), line, message)),
+        }
+    }
+}
+
+impl StdError for Error {}
+
+#[derive(Default)]
+pub struct Builder {
+    sources: Vec<(PathBuf, String)>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn file(mut self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let source = std::fs::read_to_string(&path).map_err(|err| Error::Io(path.clone(), err))?;
+
+        self.sources.push((path, source));
+
+        Ok(self)
+    }
+
+    pub fn files<P: AsRef<Path>>(self, paths: impl IntoIterator<Item = P>) -> Result<Self, Error> {
+        paths.into_iter().try_fold(self, |builder, path| builder.file(path))
+    }
+
+    pub fn source(mut self, name: impl Into<PathBuf>, source: impl Into<String>) -> Self {
+        self.sources.push((name.into(), source.into()));
+
+        self
+    }
+
+    pub fn generate(self) -> Result<Generated, Error> {
+        let documents = self
+            .sources
+            .iter()
+            .map(|(file, source)| {
+                parser::parse(source).map_err(|(line, message)| Error::Syntax {
+                    file: file.clone(),
+                    line,
+                    message,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let code = codegen::Generator::new(&documents)
+            .generate()
+            .map_err(|(idx, line, message)| Error::Semantic {
+                file: self.sources[idx].0.clone(),
+                line,
+                message,
+            })?;
+
+        Ok(Generated { code })
+    }
+}
+
+pub struct Generated {
+    code: String,
+}
+
+impl Generated {
+    pub fn as_str(&self) -> &str {
+        &self.code
+    }
+
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        std::fs::write(path.as_ref(), &self.code).map_err(|err| Error::Io(path.as_ref().to_path_buf(), err))
+    }
+}
+
+impl Display for Generated {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.code)
+    }
+}
@@ -0,0 +1,55 @@
+use std::process::ExitCode;
+
+use ndkbinder_aidl::Builder;
+
+fn main() -> ExitCode {
+    let mut output = None;
+    let mut files = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => match args.next() {
+                Some(path) => output = Some(path),
+                None => {
+                    eprintln!("missing value for {}", arg);
+                    return ExitCode::FAILURE;
+                }
+            },
+            "-h" | "--help" => {
+                println!("usage: ndkbinder-aidl [-o <output.rs>] <file.aidl>...");
+                return ExitCode::SUCCESS;
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    if files.is_empty() {
+        eprintln!("usage: ndkbinder-aidl [-o <output.rs>] <file.aidl>...");
+        return ExitCode::FAILURE;
+    }
+
+    let generated = match Builder::new().files(&files).and_then(|b| b.generate()) {
+        Ok(generated) => generated,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match output {
+        Some(path) => generated.write_to_file(path),
+        None => {
+            print!("{}", generated);
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
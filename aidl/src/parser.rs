@@ -0,0 +1,555 @@
+use crate::ast::{
+    Arg, Constant, Direction, Document, Enum, Enumerator, Expr, Field, Interface, Item, Method, Parcelable, Token, Type, Union,
+};
+
+pub type ParseError = (usize, String);
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        loop {
+            match self.chars.peek() {
+                Some('\n') => {
+                    self.line += 1;
+                    self.chars.next();
+                }
+                Some(c) if c.is_whitespace() => {
+                    self.chars.next();
+                }
+                Some('/') => {
+                    let mut ahead = self.chars.clone();
+                    ahead.next();
+                    match ahead.next() {
+                        Some('/') => {
+                            while !matches!(self.chars.next(), Some('\n') | None) {}
+                            self.line += 1;
+                        }
+                        Some('*') => {
+                            self.chars.next();
+                            self.chars.next();
+
+                            let mut last = ' ';
+                            loop {
+                                match self.chars.next() {
+                                    Some('/') if last == '*' => break,
+                                    Some(c) => {
+                                        if c == '\n' {
+                                            self.line += 1;
+                                        }
+                                        last = c;
+                                    }
+                                    None => return Err((self.line, "unterminated comment".to_string())),
+                                }
+                            }
+                        }
+                        _ => return Ok(()),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn take_while(&mut self, first: char, pred: impl Fn(char) -> bool) -> String {
+        let mut s = String::from(first);
+        while let Some(&c) = self.chars.peek() {
+            if !pred(c) {
+                break;
+            }
+            s.push(c);
+            self.chars.next();
+        }
+        s
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String, ParseError> {
+        let mut s = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\\') => {
+                    s.push('\\');
+                    if let Some(c) = self.chars.next() {
+                        s.push(c);
+                    }
+                }
+                Some(c) if c == quote => return Ok(s),
+                Some('\n') | None => return Err((self.line, "unterminated literal".to_string())),
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<(Token, usize)>, ParseError> {
+        const PUNCTS: &[&str] = &[
+            "{", "}", "(", ")", "[", "]", "<", ">", ";", ",", ".", "=", "@", "+", "-", "*", "/", "%", "|", "&", "^", "~", "!",
+            "?", ":",
+        ];
+
+        let mut tokens = Vec::new();
+        loop {
+            self.skip_trivia()?;
+
+            let c = match self.chars.next() {
+                Some(c) => c,
+                None => return Ok(tokens),
+            };
+
+            let token = if c.is_ascii_alphabetic() || c == '_' {
+                Token::Ident(self.take_while(c, |c| c.is_ascii_alphanumeric() || c == '_'))
+            } else if c.is_ascii_digit() {
+                let s = self.take_while(c, |c| c.is_ascii_alphanumeric() || c == '.' || c == '_');
+                let is_hex = s.starts_with("0x") || s.starts_with("0X");
+                if !is_hex && (s.contains('.') || s.contains('e') || s.ends_with('f') || s.ends_with('d')) {
+                    Token::Float(s)
+                } else {
+                    Token::Int(s)
+                }
+            } else if c == '"' {
+                Token::Str(self.quoted('"')?)
+            } else if c == '\'' {
+                Token::Char(self.quoted('\'')?)
+            } else {
+                let mut buf = [0u8; 4];
+                let s = &*c.encode_utf8(&mut buf);
+                match PUNCTS.iter().find(|p| **p == s) {
+                    Some(p) => Token::Punct(p),
+                    None => return Err((self.line, format!("unexpected character '{}'", c))),
+                }
+            };
+
+            tokens.push((token, self.line));
+        }
+    }
+}
+
+struct Annotation {
+    name: String,
+    params: Vec<Token>,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map(|(_, line)| *line)
+            .unwrap_or(1)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        Err((self.line(), message.into()))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn is_punct(&self, p: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punct(t)) if *t == p)
+    }
+
+    fn is_ident(&self, ident: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(t)) if t == ident)
+    }
+
+    fn eat_punct(&mut self, p: &str) -> bool {
+        if self.is_punct(p) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_ident(&mut self, ident: &str) -> bool {
+        if self.is_ident(ident) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_punct(&mut self, p: &str) -> Result<(), ParseError> {
+        if self.eat_punct(p) {
+            Ok(())
+        } else {
+            self.error(format!("expected '{}'", p))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Ident(s)) => {
+                let s = s.clone();
+                self.pos += 1;
+                Ok(s)
+            }
+            _ => self.error("expected identifier"),
+        }
+    }
+
+    fn qualified_name(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut name = vec![self.ident()?];
+        while self.eat_punct(".") {
+            name.push(self.ident()?);
+        }
+        Ok(name)
+    }
+
+    fn annotations(&mut self) -> Result<Vec<Annotation>, ParseError> {
+        let mut annotations = Vec::new();
+        while self.eat_punct("@") {
+            let name = self.ident()?;
+            let mut params = Vec::new();
+            if self.eat_punct("(") {
+                let mut depth = 1;
+                loop {
+                    match self.next() {
+                        Some(Token::Punct("(")) => depth += 1,
+                        Some(Token::Punct(")")) => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Some(t) => params.push(t),
+                        None => return self.error("unterminated annotation"),
+                    }
+                }
+            }
+            annotations.push(Annotation { name, params });
+        }
+        Ok(annotations)
+    }
+
+    fn ty(&mut self, annotations: &[Annotation]) -> Result<Type, ParseError> {
+        let line = self.line();
+        let nullable = annotations.iter().any(|a| a.name == "nullable");
+        let name = self.qualified_name()?;
+
+        let mut generics = Vec::new();
+        if self.eat_punct("<") {
+            loop {
+                let annotations = self.annotations()?;
+                generics.push(self.ty(&annotations)?);
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct(">")?;
+        }
+
+        let mut dims = Vec::new();
+        while self.eat_punct("[") {
+            if self.eat_punct("]") {
+                dims.push(None);
+            } else {
+                let size = match self.next() {
+                    Some(Token::Int(s)) => s,
+                    _ => return self.error("expected array size"),
+                };
+                self.expect_punct("]")?;
+                dims.push(Some(size));
+            }
+        }
+
+        Ok(Type {
+            name,
+            generics,
+            dims,
+            nullable,
+            line,
+        })
+    }
+
+    fn expr(&mut self, terminators: &[&str]) -> Result<Expr, ParseError> {
+        let line = self.line();
+        let mut tokens = Vec::new();
+        let mut depth = 0;
+        loop {
+            match self.peek() {
+                None => return self.error("unexpected end of file"),
+                Some(Token::Punct(p)) if depth == 0 && terminators.contains(p) => break,
+                Some(Token::Punct("(" | "{" | "[")) => depth += 1,
+                Some(Token::Punct(")" | "}" | "]")) => depth -= 1,
+                _ => {}
+            }
+            tokens.push(self.next().unwrap());
+        }
+        if tokens.is_empty() {
+            return self.error("expected expression");
+        }
+        Ok(Expr { tokens, line })
+    }
+
+    fn constant(&mut self) -> Result<Constant, ParseError> {
+        let annotations = self.annotations()?;
+        let ty = self.ty(&annotations)?;
+        let name = self.ident()?;
+        self.expect_punct("=")?;
+        let value = self.expr(&[";"])?;
+        self.expect_punct(";")?;
+        Ok(Constant { ty, name, value })
+    }
+
+    fn members(&mut self) -> Result<(Vec<Field>, Vec<Constant>), ParseError> {
+        let mut fields = Vec::new();
+        let mut constants = Vec::new();
+
+        self.expect_punct("{")?;
+        while !self.eat_punct("}") {
+            if self.eat_ident("const") {
+                constants.push(self.constant()?);
+                continue;
+            }
+
+            let annotations = self.annotations()?;
+            let ty = self.ty(&annotations)?;
+            let name = self.ident()?;
+            let default = if self.eat_punct("=") { Some(self.expr(&[";"])?) } else { None };
+            self.expect_punct(";")?;
+
+            fields.push(Field { ty, name, default });
+        }
+
+        Ok((fields, constants))
+    }
+
+    fn method(&mut self, interface_oneway: bool) -> Result<Method, ParseError> {
+        let annotations = self.annotations()?;
+        let oneway = self.eat_ident("oneway") || interface_oneway;
+        let line = self.line();
+
+        let ret = if self.eat_ident("void") {
+            None
+        } else {
+            Some(self.ty(&annotations)?)
+        };
+        let name = self.ident()?;
+
+        let mut args = Vec::new();
+        self.expect_punct("(")?;
+        if !self.eat_punct(")") {
+            loop {
+                let mut annotations = self.annotations()?;
+                let direction = if self.eat_ident("in") {
+                    Direction::In
+                } else if self.eat_ident("out") {
+                    Direction::Out
+                } else if self.eat_ident("inout") {
+                    Direction::InOut
+                } else {
+                    Direction::In
+                };
+                annotations.extend(self.annotations()?);
+                let ty = self.ty(&annotations)?;
+                let name = self.ident()?;
+                args.push(Arg { direction, ty, name });
+
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct(")")?;
+        }
+
+        let id = if self.eat_punct("=") {
+            match self.next() {
+                Some(Token::Int(s)) => Some(parse_int(&s).ok_or((line, format!("invalid transaction id '{}'", s)))? as u32),
+                _ => return self.error("expected transaction id"),
+            }
+        } else {
+            None
+        };
+        self.expect_punct(";")?;
+
+        Ok(Method {
+            name,
+            oneway,
+            ret,
+            args,
+            id,
+            line,
+        })
+    }
+
+    fn interface(&mut self, oneway: bool) -> Result<Interface, ParseError> {
+        let name = self.ident()?;
+        let mut methods = Vec::new();
+        let mut constants = Vec::new();
+
+        self.expect_punct("{")?;
+        while !self.eat_punct("}") {
+            if self.eat_ident("const") {
+                constants.push(self.constant()?);
+            } else {
+                methods.push(self.method(oneway)?);
+            }
+        }
+
+        Ok(Interface {
+            name,
+            oneway,
+            methods,
+            constants,
+        })
+    }
+
+    fn enumeration(&mut self, annotations: &[Annotation]) -> Result<Enum, ParseError> {
+        let line = self.line();
+        let name = self.ident()?;
+
+        let backing = annotations
+            .iter()
+            .find(|a| a.name == "Backing")
+            .and_then(|a| {
+                a.params.iter().find_map(|t| match t {
+                    Token::Str(s) => Some(s.clone()),
+                    _ => None,
+                })
+            })
+            .unwrap_or_else(|| "byte".to_string());
+
+        let mut enumerators = Vec::new();
+        self.expect_punct("{")?;
+        while !self.eat_punct("}") {
+            self.annotations()?;
+            let name = self.ident()?;
+            let value = if self.eat_punct("=") {
+                Some(self.expr(&[",", "}"])?)
+            } else {
+                None
+            };
+            enumerators.push(Enumerator { name, value });
+
+            if !self.eat_punct(",") {
+                self.expect_punct("}")?;
+                break;
+            }
+        }
+
+        Ok(Enum {
+            name,
+            backing: Type {
+                name: vec![backing],
+                generics: Vec::new(),
+                dims: Vec::new(),
+                nullable: false,
+                line,
+            },
+            enumerators,
+        })
+    }
+
+    fn item(&mut self) -> Result<Item, ParseError> {
+        let annotations = self.annotations()?;
+        let oneway = self.eat_ident("oneway");
+
+        if self.eat_ident("interface") {
+            return Ok(Item::Interface(self.interface(oneway)?));
+        } else if oneway {
+            return self.error("expected 'interface' after 'oneway'");
+        }
+
+        if self.eat_ident("parcelable") {
+            let name = self.ident()?;
+            if self.is_punct("{") {
+                let (fields, constants) = self.members()?;
+                return Ok(Item::Parcelable(Parcelable {
+                    name,
+                    fields: Some(fields),
+                    constants,
+                    rust_type: None,
+                }));
+            }
+
+            let mut rust_type = None;
+            while !self.eat_punct(";") {
+                match self.next() {
+                    Some(Token::Ident(s)) if s == "rust_type" => match self.next() {
+                        Some(Token::Str(path)) => rust_type = Some(path),
+                        _ => return self.error("expected string after 'rust_type'"),
+                    },
+                    Some(_) => {}
+                    None => return self.error("expected ';'"),
+                }
+            }
+            return Ok(Item::Parcelable(Parcelable {
+                name,
+                fields: None,
+                constants: Vec::new(),
+                rust_type,
+            }));
+        }
+
+        if self.eat_ident("enum") {
+            return Ok(Item::Enum(self.enumeration(&annotations)?));
+        }
+
+        if self.eat_ident("union") {
+            let name = self.ident()?;
+            let (fields, constants) = self.members()?;
+            return Ok(Item::Union(Union { name, fields, constants }));
+        }
+
+        self.error("expected 'interface', 'parcelable', 'enum' or 'union'")
+    }
+
+    fn document(&mut self) -> Result<Document, ParseError> {
+        let mut package = Vec::new();
+        if self.eat_ident("package") {
+            package = self.qualified_name()?;
+            self.expect_punct(";")?;
+        }
+
+        let mut imports = Vec::new();
+        while self.eat_ident("import") {
+            imports.push(self.qualified_name()?);
+            self.expect_punct(";")?;
+        }
+
+        let mut items = Vec::new();
+        while self.peek().is_some() {
+            items.push(self.item()?);
+        }
+
+        Ok(Document { package, imports, items })
+    }
+}
+
+pub fn parse_int(s: &str) -> Option<i128> {
+    let s = s.trim_end_matches(['l', 'L']);
+    let s = s.strip_suffix("u8").unwrap_or(s);
+
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        i128::from_str_radix(hex, 16).ok()
+    } else {
+        s.parse().ok()
+    }
+}
+
+pub fn parse(source: &str) -> Result<Document, ParseError> {
+    let tokens = Lexer {
+        chars: source.chars().peekable(),
+        line: 1,
+    }
+    .tokenize()?;
+
+    Parser { tokens, pos: 0 }.document()
+}
@@ -0,0 +1,62 @@
+use crate::{ast::Item, parser::parse, Builder, Error};
+
+const SAMPLE: &str = include_str!("../../tests/aidl/IRemote.aidl");
+
+const TYPES: &str = include_str!("../../tests/aidl/Types.aidl");
+
+const GENERATED: &str = include_str!("../../tests/aidl/generated.rs");
+
+#[test]
+fn parse_document() {
+    let document = parse(SAMPLE).unwrap();
+
+    assert_eq!(document.package, vec!["com", "example"]);
+    assert_eq!(document.imports, vec![vec!["com", "example", "types", "Point"]]);
+
+    let interface = match &document.items[..] {
+        [Item::Interface(interface)] => interface,
+        _ => panic!("expected a single interface"),
+    };
+
+    assert_eq!(interface.name, "IRemote");
+    assert_eq!(interface.constants.len(), 2);
+    assert_eq!(interface.methods.len(), 4);
+    assert!(interface.methods[3].oneway);
+    assert!(interface.methods[1].ret.as_ref().unwrap().nullable);
+}
+
+#[test]
+fn parse_syntax_error() {
+    let err = parse("package a;\ninterface IFoo {\n    void foo(int a\n}\n").unwrap_err();
+
+    assert_eq!(err.0, 4);
+}
+
+#[test]
+fn generate_code() {
+    let generated = Builder::new()
+        .source("IRemote.aidl", SAMPLE)
+        .source("Types.aidl", TYPES)
+        .generate()
+        .unwrap()
+        .to_string();
+
+    assert_eq!(generated, GENERATED);
+}
+
+#[test]
+fn generate_unknown_type() {
+    let err = Builder::new()
+        .source("IFoo.aidl", "package a;\ninterface IFoo {\n    void foo(in Missing m);\n}\n")
+        .generate()
+        .err()
+        .unwrap();
+
+    match err {
+        Error::Semantic { line, message, .. } => {
+            assert_eq!(line, 3);
+            assert!(message.contains("Missing"));
+        }
+        _ => panic!("unexpected error: {:?}", err),
+    }
+}
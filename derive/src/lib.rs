@@ -0,0 +1,407 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Fields, Generics, Ident, Index, LitInt};
+
+#[proc_macro_derive(Read)]
+pub fn derive_read(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_read(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Write)]
+pub fn derive_write(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_write(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn add_bounds(mut generics: Generics, bounds: TokenStream2) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bounds));
+    }
+
+    generics
+}
+
+fn struct_fields(input: &DeriveInput) -> syn::Result<&Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "parcelables can only be derived for structs and enums",
+        )),
+    }
+}
+
+struct Backing {
+    repr: Ident,
+    values: Vec<(Ident, TokenStream2)>,
+    unknown: Option<Ident>,
+}
+
+fn enum_backing(input: &DeriveInput, data: &DataEnum) -> syn::Result<Backing> {
+    let mut repr = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if ["i8", "i32", "i64"].iter().any(|t| meta.path.is_ident(t)) {
+                repr = meta.path.get_ident().cloned();
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let repr =
+        repr.ok_or_else(|| syn::Error::new_spanned(&input.ident, "enums must be #[repr(i8)], #[repr(i32)] or #[repr(i64)]"))?;
+
+    let mut values = Vec::new();
+    let mut unknown = None;
+    let mut base = None;
+    let mut offset = 0u64;
+
+    for variant in &data.variants {
+        match &variant.fields {
+            Fields::Unit => {
+                if let Some((_, expr)) = &variant.discriminant {
+                    base = Some(quote!((#expr)));
+                    offset = 0;
+                }
+
+                let offset_lit = LitInt::new(&format!("{}{}", offset, repr), variant.ident.span());
+                let value = match &base {
+                    Some(base) => quote!(#base + #offset_lit),
+                    None => quote!(#offset_lit),
+                };
+
+                values.push((variant.ident.clone(), value));
+                offset += 1;
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 && unknown.is_none() => {
+                unknown = Some(variant.ident.clone());
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "enum variants must be unit variants or a single tuple variant holding unknown values",
+                ))
+            }
+        }
+    }
+
+    Ok(Backing { repr, values, unknown })
+}
+
+fn expand_enum(input: &DeriveInput, data: &DataEnum, read: bool) -> syn::Result<TokenStream2> {
+    let Backing { repr, values, unknown } = enum_backing(input, data)?;
+
+    let name = &input.ident;
+    let consts = values
+        .iter()
+        .enumerate()
+        .map(|(idx, (ident, _))| Ident::new(&format!("__NDKBINDER_VALUE_{}", idx), ident.span()))
+        .collect::<Vec<_>>();
+    let variants = values.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+    let exprs = values.iter().map(|(_, value)| value).collect::<Vec<_>>();
+
+    if read {
+        let fallback = match &unknown {
+            Some(unknown) => quote!(value => ::std::result::Result::Ok(#name::#unknown(value))),
+            None => quote!(_ => ::std::result::Result::Err(::ndkbinder::Status::bad_value())),
+        };
+
+        Ok(quote! {
+            impl ::ndkbinder::Read for #name {
+                fn read(parcel: &::ndkbinder::Parcel) -> ::std::result::Result<Self, ::ndkbinder::Status> {
+                    let value = parcel.read::<#repr>()?;
+
+                    #(const #consts: #repr = #exprs;)*
+
+                    match value {
+                        #(#consts => ::std::result::Result::Ok(#name::#variants),)*
+                        #fallback,
+                    }
+                }
+            }
+
+            impl ::ndkbinder::ReadArray for #name {
+                fn read_array(
+                    parcel: &::ndkbinder::Parcel,
+                ) -> ::std::result::Result<::std::option::Option<::std::vec::Vec<Self>>, ::ndkbinder::Status> {
+                    #(const #consts: #repr = #exprs;)*
+
+                    <#repr as ::ndkbinder::ReadArray>::read_array(parcel)?
+                        .map(|values| {
+                            values
+                                .into_iter()
+                                .map(|value| match value {
+                                    #(#consts => ::std::result::Result::Ok(#name::#variants),)*
+                                    #fallback,
+                                })
+                                .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()
+                        })
+                        .transpose()
+                }
+            }
+        })
+    } else {
+        let unknown = unknown.map(|unknown| quote!(#name::#unknown(value) => *value,));
+
+        Ok(quote! {
+            impl ::ndkbinder::Write for #name {
+                fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> ::std::result::Result<(), ::ndkbinder::Status> {
+                    let value: #repr = match self {
+                        #(#name::#variants => #exprs,)*
+                        #unknown
+                    };
+
+                    parcel.write(&value)
+                }
+            }
+
+            impl ::ndkbinder::WriteArray for #name {
+                fn write_array(
+                    parcel: &mut ::ndkbinder::Parcel,
+                    values: ::std::option::Option<&[Self]>,
+                ) -> ::std::result::Result<(), ::ndkbinder::Status> {
+                    let values = values.map(|values| {
+                        values
+                            .iter()
+                            .map(|value| match value {
+                                #(#name::#variants => #exprs,)*
+                                #unknown
+                            })
+                            .collect::<::std::vec::Vec<#repr>>()
+                    });
+
+                    <#repr as ::ndkbinder::WriteArray>::write_array(parcel, values.as_deref())
+                }
+            }
+        })
+    }
+}
+
+fn is_backed(input: &DeriveInput) -> bool {
+    input.attrs.iter().any(|attr| attr.path().is_ident("repr"))
+}
+
+fn union_variants(data: &DataEnum) -> syn::Result<Vec<&Ident>> {
+    data.variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(&variant.ident),
+            _ => Err(syn::Error::new_spanned(
+                variant,
+                "union variants must be tuple variants holding exactly one value",
+            )),
+        })
+        .collect()
+}
+
+fn tag_name(ident: &Ident) -> Ident {
+    let mut name = String::from("TAG");
+    for c in ident.to_string().chars() {
+        if c.is_uppercase() {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+    }
+
+    Ident::new(&name, ident.span())
+}
+
+fn expand_union(input: &DeriveInput, data: &DataEnum, read: bool) -> syn::Result<TokenStream2> {
+    let variants = union_variants(data)?;
+    let tags = (0..variants.len() as i32).collect::<Vec<_>>();
+
+    let name = &input.ident;
+
+    if read {
+        let generics = add_bounds(input.generics.clone(), quote!(::ndkbinder::Read));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        Ok(quote! {
+            impl #impl_generics ::ndkbinder::Read for #name #ty_generics #where_clause {
+                fn read(parcel: &::ndkbinder::Parcel) -> ::std::result::Result<Self, ::ndkbinder::Status> {
+                    <Self as ::ndkbinder::ReadNullable>::read_nullable(parcel)?
+                        .ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))
+                }
+            }
+
+            impl #impl_generics ::ndkbinder::ReadNullable for #name #ty_generics #where_clause {
+                fn read_nullable(
+                    parcel: &::ndkbinder::Parcel,
+                ) -> ::std::result::Result<::std::option::Option<Self>, ::ndkbinder::Status> {
+                    if parcel.read::<i32>()? == 0 {
+                        return ::std::result::Result::Ok(::std::option::Option::None);
+                    }
+
+                    match parcel.read::<i32>()? {
+                        #(#tags => ::std::result::Result::Ok(::std::option::Option::Some(#name::#variants(parcel.read()?))),)*
+                        _ => ::std::result::Result::Err(::ndkbinder::Status::bad_value()),
+                    }
+                }
+            }
+
+            impl #impl_generics ::ndkbinder::ReadArray for #name #ty_generics #where_clause {}
+        })
+    } else {
+        let generics = add_bounds(input.generics.clone(), quote!(::ndkbinder::Write));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let consts = variants.iter().map(|v| tag_name(v)).collect::<Vec<_>>();
+
+        Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(pub const #consts: i32 = #tags;)*
+
+                pub fn tag(&self) -> i32 {
+                    match self {
+                        #(#name::#variants(_) => #tags,)*
+                    }
+                }
+            }
+
+            impl #impl_generics ::ndkbinder::Write for #name #ty_generics #where_clause {
+                fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> ::std::result::Result<(), ::ndkbinder::Status> {
+                    parcel.write(&1i32)?;
+                    parcel.write(&self.tag())?;
+
+                    match self {
+                        #(#name::#variants(value) => parcel.write(value),)*
+                    }
+                }
+            }
+
+            impl #impl_generics ::ndkbinder::WriteNullable for #name #ty_generics #where_clause {}
+
+            impl #impl_generics ::ndkbinder::WriteArray for #name #ty_generics #where_clause {}
+        })
+    }
+}
+
+fn expand_read(input: DeriveInput) -> syn::Result<TokenStream2> {
+    match &input.data {
+        Data::Enum(data) if is_backed(&input) => return expand_enum(&input, data, true),
+        Data::Enum(data) => return expand_union(&input, data, true),
+        _ => {}
+    }
+
+    let fields = struct_fields(&input)?;
+
+    let name = &input.ident;
+    let mut generics = add_bounds(input.generics.clone(), quote!(::ndkbinder::Read));
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: ::std::default::Default));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let reads = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|f| {
+                let ident = &f.ident;
+
+                quote!(if reader.has_more_data() { value.#ident = reader.read()?; })
+            })
+            .collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|idx| {
+                let idx = Index::from(idx);
+
+                quote!(if reader.has_more_data() { value.#idx = reader.read()?; })
+            })
+            .collect::<Vec<_>>(),
+        Fields::Unit => Vec::new(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::ndkbinder::Read for #name #ty_generics #where_clause {
+            fn read(parcel: &::ndkbinder::Parcel) -> ::std::result::Result<Self, ::ndkbinder::Status> {
+                <Self as ::ndkbinder::ReadNullable>::read_nullable(parcel)?
+                    .ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))
+            }
+        }
+
+        impl #impl_generics ::ndkbinder::ReadNullable for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn read_nullable(
+                parcel: &::ndkbinder::Parcel,
+            ) -> ::std::result::Result<::std::option::Option<Self>, ::ndkbinder::Status> {
+                if parcel.read::<i32>()? == 0 {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+
+                parcel.read_sized(|reader| {
+                    #[allow(unused_mut)]
+                    let mut value = <Self as ::std::default::Default>::default();
+
+                    #(#reads)*
+
+                    ::std::result::Result::Ok(::std::option::Option::Some(value))
+                })
+            }
+        }
+
+        impl #impl_generics ::ndkbinder::ReadArray for #name #ty_generics #where_clause {}
+    })
+}
+
+fn expand_write(input: DeriveInput) -> syn::Result<TokenStream2> {
+    match &input.data {
+        Data::Enum(data) if is_backed(&input) => return expand_enum(&input, data, false),
+        Data::Enum(data) => return expand_union(&input, data, false),
+        _ => {}
+    }
+
+    let fields = struct_fields(&input)?;
+
+    let name = &input.ident;
+    let generics = add_bounds(input.generics.clone(), quote!(::ndkbinder::Write));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let writes = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|f| {
+                let ident = &f.ident;
+
+                quote!(parcel.write(&self.#ident)?;)
+            })
+            .collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|idx| {
+                let idx = Index::from(idx);
+
+                quote!(parcel.write(&self.#idx)?;)
+            })
+            .collect::<Vec<_>>(),
+        Fields::Unit => Vec::new(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::ndkbinder::Write for #name #ty_generics #where_clause {
+            fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> ::std::result::Result<(), ::ndkbinder::Status> {
+                parcel.write(&1i32)?;
+                parcel.write_sized(|parcel| {
+                    #(#writes)*
+
+                    ::std::result::Result::Ok(())
+                })
+            }
+        }
+
+        impl #impl_generics ::ndkbinder::WriteNullable for #name #ty_generics #where_clause {}
+
+        impl #impl_generics ::ndkbinder::WriteArray for #name #ty_generics #where_clause {}
+    })
+}
@@ -0,0 +1,181 @@
+use std::{
+    future::Future,
+    os::fd::RawFd,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+};
+
+use crate::{pool::pool, Class, Code, Flags, IBinder, Parcel, Status};
+
+struct Shared<O> {
+    result: Option<O>,
+    waker: Option<Waker>,
+}
+
+pub struct Transaction<O> {
+    shared: Arc<Mutex<Shared<Result<O, Status>>>>,
+}
+
+impl<O> Future for Transaction<O> {
+    type Output = Result<O, Status>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+
+        match shared.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl IBinder {
+    pub fn transact_async<O, D, R>(&self, code: u32, data: D, reply: R, flags: Flags) -> Transaction<O>
+    where
+        O: Send + 'static,
+        D: FnOnce(&mut Parcel) -> Result<(), Status> + Send + 'static,
+        R: FnOnce(Option<&Parcel>) -> Result<O, Status> + Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(Shared {
+            result: None,
+            waker: None,
+        }));
+
+        let binder = self.clone();
+        let completion = shared.clone();
+
+        let spawned = pool().execute(Box::new(move || {
+            let result = binder.transact(code, data, reply, flags);
+
+            let mut shared = completion.lock().unwrap();
+            shared.result = Some(result);
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        }));
+
+        if spawned.is_err() {
+            shared.lock().unwrap().result = Some(Err(Status::with_code(Code::NoMemory)));
+        }
+
+        Transaction { shared }
+    }
+}
+
+pub trait AsyncRuntime: Send + Sync {
+    fn block_on<F: Future>(&self, future: F) -> F::Output;
+}
+
+pub struct BlockingRuntime;
+
+impl AsyncRuntime for BlockingRuntime {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        struct ThreadWaker(Thread);
+
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => std::thread::park(),
+            }
+        }
+    }
+}
+
+pub trait AsyncClass: Send + Sync {
+    const INTERFACE_NAME: &'static str;
+
+    #[cfg(feature = "api-33")]
+    fn disable_interface_token_header() -> bool {
+        false
+    }
+
+    fn enable_status_header() -> bool {
+        false
+    }
+
+    #[cfg(feature = "stability")]
+    fn stability() -> Option<crate::Stability> {
+        None
+    }
+
+    #[cfg(feature = "calling_sid")]
+    fn requesting_sid() -> bool {
+        false
+    }
+
+    fn on_transact_async<'a>(
+        &'a self,
+        code: u32,
+        data: &'a Parcel,
+        reply: Option<&'a mut Parcel>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Status>> + 'a>>;
+
+    fn on_dump(&self, fd: RawFd, args: &[&str]) -> Result<(), Status> {
+        let _ = fd;
+        let _ = args;
+
+        Err(Status::unknown_transaction())
+    }
+}
+
+pub struct AsyncService<T: AsyncClass, R: AsyncRuntime> {
+    service: T,
+    runtime: R,
+}
+
+impl<T: AsyncClass, R: AsyncRuntime> AsyncService<T, R> {
+    pub fn new(service: T, runtime: R) -> Self {
+        AsyncService { service, runtime }
+    }
+
+    pub fn service(&self) -> &T {
+        &self.service
+    }
+}
+
+impl<T: AsyncClass, R: AsyncRuntime> Class for AsyncService<T, R> {
+    const INTERFACE_NAME: &'static str = T::INTERFACE_NAME;
+
+    #[cfg(feature = "api-33")]
+    fn disable_interface_token_header() -> bool {
+        T::disable_interface_token_header()
+    }
+
+    fn enable_status_header() -> bool {
+        T::enable_status_header()
+    }
+
+    #[cfg(feature = "stability")]
+    fn stability() -> Option<crate::Stability> {
+        T::stability()
+    }
+
+    #[cfg(feature = "calling_sid")]
+    fn requesting_sid() -> bool {
+        T::requesting_sid()
+    }
+
+    fn on_transact(&self, code: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+        self.runtime.block_on(self.service.on_transact_async(code, data, reply))
+    }
+
+    fn on_dump(&self, fd: RawFd, args: &[&str]) -> Result<(), Status> {
+        self.service.on_dump(fd, args)
+    }
+}
@@ -3,21 +3,44 @@ use std::{
     fmt::{Debug, Formatter},
     os::fd::RawFd,
     ptr::null_mut,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    time::Duration,
 };
 
 use crate::{
+    pool::pool,
     sys::{
-        AIBinder, AIBinder_DeathRecipient, AIBinder_DeathRecipient_delete, AIBinder_DeathRecipient_new, AIBinder_Weak,
-        AIBinder_Weak_delete, AIBinder_Weak_new, AIBinder_Weak_promote, AIBinder_decStrong, AIBinder_dump,
-        AIBinder_getCallingPid, AIBinder_getCallingUid, AIBinder_incStrong, AIBinder_isAlive, AIBinder_isRemote,
-        AIBinder_linkToDeath, AIBinder_ping, AIBinder_prepareTransaction, AIBinder_transact, AIBinder_unlinkToDeath, AParcel,
-        FLAG_ONEWAY,
+        AIBinder, AIBinder_Class, AIBinder_DeathRecipient, AIBinder_DeathRecipient_delete, AIBinder_DeathRecipient_new,
+        AIBinder_Weak, AIBinder_Weak_delete, AIBinder_Weak_new, AIBinder_Weak_promote, AIBinder_decStrong, AIBinder_dump,
+        AIBinder_getCallingPid, AIBinder_getCallingUid, AIBinder_getClass, AIBinder_getUserData, AIBinder_incStrong,
+        AIBinder_isAlive, AIBinder_isRemote, AIBinder_linkToDeath, AIBinder_ping, AIBinder_prepareTransaction, AIBinder_transact,
+        AIBinder_unlinkToDeath, AParcel,
     },
-    Parcel, Status,
+    Code, DefinedClass, Parcel, Status,
 };
 
-pub enum Flags {
-    Oneway,
+pub const FIRST_CALL_TRANSACTION: u32 = crate::sys::FIRST_CALL_TRANSACTION;
+
+pub const LAST_CALL_TRANSACTION: u32 = crate::sys::LAST_CALL_TRANSACTION;
+
+// defined in binder_ibinder_platform.h, which is not part of the NDK
+#[cfg(feature = "api-31")]
+const FLAG_CLEAR_BUF: u32 = 0x20;
+
+const FLAG_PRIVATE_VENDOR: u32 = 0x10000000;
+
+bitflags::bitflags! {
+    #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+    pub struct Flags: u32 {
+        const ONEWAY = crate::sys::FLAG_ONEWAY;
+        #[cfg(feature = "api-31")]
+        const CLEAR_BUF = FLAG_CLEAR_BUF;
+        const PRIVATE_VENDOR = FLAG_PRIVATE_VENDOR;
+    }
 }
 
 pub struct IBinder {
@@ -42,6 +65,12 @@ impl Clone for IBinder {
     }
 }
 
+impl AsRef<IBinder> for IBinder {
+    fn as_ref(&self) -> &IBinder {
+        self
+    }
+}
+
 impl Drop for IBinder {
     fn drop(&mut self) {
         unsafe { AIBinder_decStrong(self.ptr) }
@@ -135,6 +164,33 @@ impl IBinder {
         unsafe { AIBinder_isRemote(self.ptr) }
     }
 
+    pub fn downcast_local<T: DefinedClass>(&self) -> Option<&T> {
+        unsafe {
+            if AIBinder_isRemote(self.ptr) || AIBinder_getClass(self.ptr) != T::_class() as *const AIBinder_Class {
+                return None;
+            }
+
+            AIBinder_getUserData(self.ptr).cast::<T>().as_ref()
+        }
+    }
+
+    #[cfg(feature = "api-31")]
+    pub fn get_interface_descriptor(&self) -> Option<String> {
+        unsafe {
+            let class = AIBinder_getClass(self.ptr);
+            if class.is_null() {
+                return None;
+            }
+
+            let descriptor = crate::sys::AIBinder_Class_getDescriptor(class);
+            if descriptor.is_null() {
+                None
+            } else {
+                Some(std::ffi::CStr::from_ptr(descriptor).to_string_lossy().into_owned())
+            }
+        }
+    }
+
     pub fn ping(&self) -> Result<(), Status> {
         unsafe { Status::from_raw_status_code(AIBinder_ping(self.ptr)).err(|| ()) }
     }
@@ -145,41 +201,149 @@ impl IBinder {
         }
     }
 
-    pub fn transact<O, D, R>(&self, code: u32, data: D, reply: R, flags: &[Flags]) -> Result<O, Status>
+    pub fn transact<O, D, R>(&self, code: u32, data: D, reply: R, flags: Flags) -> Result<O, Status>
     where
         D: FnOnce(&mut Parcel) -> Result<(), Status>,
         R: FnOnce(Option<&Parcel>) -> Result<O, Status>,
+    {
+        reply(self.transact_raw(code, data, flags)?.as_ref())
+    }
+
+    pub(crate) fn transact_raw<D>(&self, code: u32, data: D, flags: Flags) -> Result<Option<Parcel>, Status>
+    where
+        D: FnOnce(&mut Parcel) -> Result<(), Status>,
+    {
+        self.transact_prepared(code, self.prepare_transaction(data)?, flags)
+    }
+
+    pub(crate) fn prepare_transaction<D>(&self, data: D) -> Result<Parcel, Status>
+    where
+        D: FnOnce(&mut Parcel) -> Result<(), Status>,
     {
         unsafe {
             let mut data_parcel: *mut AParcel = null_mut();
 
             Status::from_raw_status_code(AIBinder_prepareTransaction(self.ptr, &mut data_parcel)).err(|| ())?;
 
-            data(&mut Parcel::from_borrow_raw(data_parcel))?;
+            let mut parcel = Parcel::from_raw(data_parcel);
+            data(&mut parcel)?;
 
-            let mut reply_parcel: *mut AParcel = null_mut();
+            Ok(parcel)
+        }
+    }
 
-            let flags = flags.iter().fold(0, |v, f| match f {
-                Flags::Oneway => v | FLAG_ONEWAY,
-            });
+    pub(crate) fn transact_prepared(&self, code: u32, data: Parcel, flags: Flags) -> Result<Option<Parcel>, Status> {
+        unsafe {
+            let mut data_parcel = data.into_raw();
+            let mut reply_parcel: *mut AParcel = null_mut();
 
-            Status::from_raw_status_code(AIBinder_transact(self.ptr, code, &mut data_parcel, &mut reply_parcel, flags))
-                .err(|| ())?;
+            Status::from_raw_status_code(AIBinder_transact(
+                self.ptr,
+                code,
+                &mut data_parcel,
+                &mut reply_parcel,
+                flags.bits(),
+            ))
+            .err(|| ())?;
 
-            let reply_parcel = if reply_parcel.is_null() {
-                None
+            if reply_parcel.is_null() {
+                Ok(None)
             } else {
-                Some(Parcel::from_raw(reply_parcel))
-            };
+                Ok(Some(Parcel::from_raw(reply_parcel)))
+            }
+        }
+    }
+
+    pub fn transact_with_status_header<O, D, R>(&self, code: u32, data: D, reply: R, flags: Flags) -> Result<O, Status>
+    where
+        D: FnOnce(&mut Parcel) -> Result<(), Status>,
+        R: FnOnce(Option<&Parcel>) -> Result<O, Status>,
+    {
+        self.transact(code, data, read_status_header(reply, flags), flags)
+    }
+}
+
+impl IBinder {
+    /// The data is written on the calling thread, the transaction runs on a thread pool shared with `transact_async` and
+    /// limited to 16 threads. On timeout a transaction that was already sent is not cancelled, its reply is dropped when
+    /// it arrives.
+    pub fn transact_with_timeout<O, D, R>(
+        &self,
+        code: u32,
+        data: D,
+        reply: R,
+        flags: Flags,
+        timeout: Duration,
+    ) -> Result<O, Status>
+    where
+        D: FnOnce(&mut Parcel) -> Result<(), Status>,
+        R: FnOnce(Option<&Parcel>) -> Result<O, Status>,
+    {
+        let data = self.prepare_transaction(data)?;
+
+        reply(self.transact_prepared_with_timeout(code, data, flags, timeout)?.as_ref())
+    }
+
+    pub(crate) fn transact_prepared_with_timeout(
+        &self,
+        code: u32,
+        data: Parcel,
+        flags: Flags,
+        timeout: Duration,
+    ) -> Result<Option<Parcel>, Status> {
+        struct SendParcel(Parcel);
+
+        unsafe impl Send for SendParcel {}
+
+        let binder = self.clone();
+        let data = SendParcel(data);
+        let (tx, rx) = mpsc::sync_channel(1);
+        let expired = Arc::new(AtomicBool::new(false));
+
+        let pending = expired.clone();
+        pool()
+            .execute(Box::new(move || {
+                let data = data;
+                // a transaction still queued when the caller timed out is never sent
+                if pending.load(Ordering::SeqCst) {
+                    return;
+                }
+                // the receiver is gone once the caller timed out, the late reply is dropped here
+                let _ = tx.send(binder.transact_prepared(code, data.0, flags).map(|r| r.map(SendParcel)));
+            }))
+            .map_err(|_| Status::with_code(Code::NoMemory))?;
+
+        match rx.recv_timeout(timeout) {
+            Ok(result) => result.map(|r| r.map(|r| r.0)),
+            Err(RecvTimeoutError::Timeout) => {
+                expired.store(true, Ordering::SeqCst);
+
+                Err(Status::with_code(Code::TimedOut))
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(Status::with_code(Code::UnknownError)),
+        }
+    }
+}
+
+pub(crate) fn read_status_header<O, R>(reply: R, flags: Flags) -> impl FnOnce(Option<&Parcel>) -> Result<O, Status>
+where
+    R: FnOnce(Option<&Parcel>) -> Result<O, Status>,
+{
+    let oneway = flags.contains(Flags::ONEWAY);
 
-            reply(reply_parcel.as_ref())
+    move |parcel| {
+        if let Some(parcel) = parcel.filter(|_| !oneway) {
+            parcel.read::<Status>()?.err(|| ())?;
         }
+
+        reply(parcel)
     }
 }
 
 #[cfg(feature = "jni")]
 impl IBinder {
     pub unsafe fn from_java(env: *mut jni_sys::JNIEnv, obj: jni_sys::jobject) -> Option<Self> {
+        #[cfg_attr(feature = "host-fake", allow(improper_ctypes))]
         extern "C" {
             fn AIBinder_fromJavaBinder(env: *mut jni_sys::JNIEnv, obj: jni_sys::jobject) -> *mut AIBinder;
         }
@@ -193,6 +357,7 @@ impl IBinder {
     }
 
     pub fn as_java(&self, env: *mut jni_sys::JNIEnv) -> jni_sys::jobject {
+        #[cfg_attr(feature = "host-fake", allow(improper_ctypes))]
         extern "C" {
             fn AIBinder_toJavaBinder(env: *mut jni_sys::JNIEnv, obj: *mut AIBinder) -> jni_sys::jobject;
         }
@@ -266,6 +431,13 @@ impl WeakIBinder {
 
 pub trait DeathRecipient: Send {
     fn on_dead(self);
+
+    #[cfg(feature = "api-33")]
+    fn on_unlinked(self)
+    where
+        Self: Sized,
+    {
+    }
 }
 
 pub struct LinkedDeathRecipient<B: AsRef<IBinder>, R: DeathRecipient> {
@@ -274,6 +446,12 @@ pub struct LinkedDeathRecipient<B: AsRef<IBinder>, R: DeathRecipient> {
     recipient_spec: *mut AIBinder_DeathRecipient,
 }
 
+impl<B: AsRef<IBinder>, R: DeathRecipient> LinkedDeathRecipient<B, R> {
+    pub fn binder(&self) -> &B {
+        &self.binder
+    }
+}
+
 impl<B: AsRef<IBinder>, R: DeathRecipient> Drop for LinkedDeathRecipient<B, R> {
     fn drop(&mut self) {
         unsafe {
@@ -281,6 +459,8 @@ impl<B: AsRef<IBinder>, R: DeathRecipient> Drop for LinkedDeathRecipient<B, R> {
 
             AIBinder_DeathRecipient_delete(self.recipient_spec);
 
+            // with api-33 the recipient is released by on_unlinked, which may still race with on_dead
+            #[cfg(not(feature = "api-33"))]
             drop(Box::from_raw(self.recipient));
         }
     }
@@ -298,20 +478,40 @@ impl IBinder {
                 }
             }
 
+            #[cfg(feature = "api-33")]
+            unsafe extern "C" fn on_unlinked<R: DeathRecipient>(cookies: *mut c_void) {
+                if let Some(r) = Box::from_raw(cookies.cast::<Option<R>>()).take() {
+                    r.on_unlinked();
+                }
+            }
+
             let recipient_spec = AIBinder_DeathRecipient_new(Some(on_dead::<R>));
             let recipient: *mut Option<R> = Box::into_raw(Box::new(Some(recipient)));
-            let linked = LinkedDeathRecipient {
-                binder,
-                recipient,
+
+            #[cfg(feature = "api-33")]
+            crate::sys::AIBinder_DeathRecipient_setOnUnlinked(recipient_spec, Some(on_unlinked::<R>));
+
+            let status = Status::from_raw_status_code(AIBinder_linkToDeath(
+                binder.as_ref().as_raw(),
                 recipient_spec,
-            };
+                recipient.cast(),
+            ));
+
+            if let Err(err) = status.err(|| ()) {
+                AIBinder_DeathRecipient_delete(recipient_spec);
+
+                // a failed link has already released the recipient through on_unlinked
+                #[cfg(not(feature = "api-33"))]
+                drop(Box::from_raw(recipient));
 
-            Status::from_raw_status_code(AIBinder_linkToDeath(
-                linked.binder.as_ref().as_raw(),
+                return Err(err);
+            }
+
+            Ok(LinkedDeathRecipient {
+                binder,
+                recipient,
                 recipient_spec,
-                linked.recipient.cast(),
-            ))
-            .err(|| linked)
+            })
         }
     }
 }
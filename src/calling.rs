@@ -0,0 +1,97 @@
+#[cfg(feature = "calling_sid")]
+use std::{
+    error::Error,
+    ffi::{c_char, CStr},
+    fmt::{Debug, Display, Formatter},
+};
+
+use crate::IBinder;
+#[cfg(feature = "calling_sid")]
+use crate::{symbol::Symbol, sys::AIBinder, SymbolNotFound};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CallingContext {
+    pub pid: u32,
+    pub uid: u32,
+    #[cfg(feature = "calling_sid")]
+    pub sid: Option<String>,
+}
+
+impl CallingContext {
+    pub fn current() -> CallingContext {
+        CallingContext {
+            pid: IBinder::get_calling_pid(),
+            uid: IBinder::get_calling_uid(),
+            #[cfg(feature = "calling_sid")]
+            sid: IBinder::get_calling_sid(),
+        }
+    }
+}
+
+#[cfg(feature = "calling_sid")]
+pub enum CallingSidError {
+    SymbolNotFound(SymbolNotFound),
+    RemoteBinder,
+}
+
+#[cfg(feature = "calling_sid")]
+impl Debug for CallingSidError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallingSidError::SymbolNotFound(err) => Debug::fmt(err, f),
+            CallingSidError::RemoteBinder => f.write_str("requesting sid of remote binder can not be changed"),
+        }
+    }
+}
+
+#[cfg(feature = "calling_sid")]
+impl Display for CallingSidError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallingSidError::SymbolNotFound(err) => Display::fmt(err, f),
+            CallingSidError::RemoteBinder => f.write_str("requesting sid of remote binder can not be changed"),
+        }
+    }
+}
+
+#[cfg(feature = "calling_sid")]
+impl Error for CallingSidError {}
+
+#[cfg(feature = "calling_sid")]
+impl From<SymbolNotFound> for CallingSidError {
+    fn from(value: SymbolNotFound) -> Self {
+        CallingSidError::SymbolNotFound(value)
+    }
+}
+
+#[cfg(feature = "calling_sid")]
+impl IBinder {
+    pub fn set_requesting_sid(&self, requesting_sid: bool) -> Result<(), CallingSidError> {
+        static FUNC: Symbol<unsafe extern "C" fn(*mut AIBinder, bool)> = unsafe { Symbol::new(c"AIBinder_setRequestingSid") };
+
+        if self.is_remote() {
+            return Err(CallingSidError::RemoteBinder);
+        }
+
+        let func = FUNC.get()?;
+
+        unsafe { func(self.as_raw(), requesting_sid) };
+
+        Ok(())
+    }
+
+    pub fn get_calling_sid() -> Option<String> {
+        static FUNC: Symbol<unsafe extern "C" fn() -> *const c_char> = unsafe { Symbol::new(c"AIBinder_getCallingSid") };
+
+        let func = FUNC.get().ok()?;
+
+        unsafe {
+            let sid = func();
+            if sid.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(sid).to_string_lossy().into_owned())
+            }
+        }
+    }
+}
@@ -1,3 +1,8 @@
+#[cfg(any(feature = "stability", feature = "calling_sid"))]
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+};
 use std::{
     ffi::{CStr, CString},
     ops::Deref,
@@ -7,12 +12,16 @@ use std::{
     },
 };
 
+#[cfg(feature = "calling_sid")]
+use crate::CallingSidError;
+#[cfg(feature = "stability")]
+use crate::StabilityError;
 use crate::{
     sys::{
         binder_status_t, transaction_code_t, AIBinder, AIBinder_Class, AIBinder_Class_define, AIBinder_Class_setOnDump,
         AIBinder_getUserData, AIBinder_new, AParcel,
     },
-    Code, IBinder, Parcel, Status,
+    Code, Exception, IBinder, Parcel, Status,
 };
 
 pub trait Class: Send + Sync {
@@ -23,6 +32,20 @@ pub trait Class: Send + Sync {
         false
     }
 
+    fn enable_status_header() -> bool {
+        false
+    }
+
+    #[cfg(feature = "stability")]
+    fn stability() -> Option<crate::Stability> {
+        None
+    }
+
+    #[cfg(feature = "calling_sid")]
+    fn requesting_sid() -> bool {
+        false
+    }
+
     fn on_transact(&self, code: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status>;
 
     fn on_dump(&self, fd: RawFd, args: &[&str]) -> Result<(), Status> {
@@ -33,6 +56,14 @@ pub trait Class: Send + Sync {
     }
 }
 
+/// # Safety
+///
+/// `_class` must return the class defined for `Self` by `_define_class_impl::<Self>`, use `define_class!` to implement it.
+pub unsafe trait DefinedClass: Class {
+    #[doc(hidden)]
+    fn _class() -> usize;
+}
+
 #[doc(hidden)]
 pub fn _define_class_impl<T: Class>() -> usize {
     let interface_name = CString::new(T::INTERFACE_NAME).unwrap();
@@ -63,9 +94,28 @@ pub fn _define_class_impl<T: Class>() -> usize {
             Some(Parcel::from_borrow_raw(reply.cast()))
         };
 
+        let mut header_end = 0;
+        if T::enable_status_header() {
+            if let Some(reply) = reply.as_mut() {
+                if let Err(err) = reply.write(&Status::with_code(Code::Ok)) {
+                    return err.get_code().as_raw();
+                }
+
+                header_end = reply.get_data_position();
+            }
+        }
+
         match (&*obj.cast::<T>()).on_transact(code, &data, reply.as_mut()) {
             Ok(_) => Code::Ok.as_raw(),
-            Err(err) => err.get_code().as_raw(),
+            Err(err) => match reply.as_mut() {
+                Some(reply) if T::enable_status_header() && err.get_exception() != Exception::TransactionFailed => {
+                    match clear_reply(reply, header_end).and_then(|_| reply.write(&err)) {
+                        Ok(_) => Code::Ok.as_raw(),
+                        Err(err) => err.get_code().as_raw(),
+                    }
+                }
+                _ => err.get_code().as_raw(),
+            },
         }
     }
 
@@ -106,8 +156,61 @@ pub fn _define_class_impl<T: Class>() -> usize {
     }
 }
 
-#[doc(hidden)]
-pub fn _new_ibinder_with_class<T: Class>(class: usize, binder: T) -> IBinder {
+// partial results written before the error must not trail the error status
+#[cfg_attr(feature = "api-31", allow(unused_variables))]
+fn clear_reply(reply: &mut Parcel, header_end: u32) -> Result<(), Status> {
+    #[cfg(feature = "api-31")]
+    {
+        reply.reset();
+
+        Ok(())
+    }
+
+    // without AParcel_reset the reply can not be truncated, it is reused only when it holds nothing but the status header
+    #[cfg(not(feature = "api-31"))]
+    if reply.get_data_position() == header_end {
+        reply.set_data_position(0)
+    } else {
+        Err(Status::with_code(Code::FailedTransaction))
+    }
+}
+
+#[cfg(any(feature = "stability", feature = "calling_sid"))]
+pub enum ClassError {
+    #[cfg(feature = "stability")]
+    Stability(StabilityError),
+    #[cfg(feature = "calling_sid")]
+    RequestingSid(CallingSidError),
+}
+
+#[cfg(any(feature = "stability", feature = "calling_sid"))]
+impl Debug for ClassError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            #[cfg(feature = "stability")]
+            ClassError::Stability(ref err) => f.write_fmt(format_args!("mark stability: {:?}", err)),
+            #[cfg(feature = "calling_sid")]
+            ClassError::RequestingSid(ref err) => f.write_fmt(format_args!("request calling sid: {:?}", err)),
+        }
+    }
+}
+
+#[cfg(any(feature = "stability", feature = "calling_sid"))]
+impl Display for ClassError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            #[cfg(feature = "stability")]
+            ClassError::Stability(ref err) => f.write_fmt(format_args!("mark stability: {}", err)),
+            #[cfg(feature = "calling_sid")]
+            ClassError::RequestingSid(ref err) => f.write_fmt(format_args!("request calling sid: {}", err)),
+        }
+    }
+}
+
+#[cfg(any(feature = "stability", feature = "calling_sid"))]
+impl Error for ClassError {}
+
+fn new_ibinder<T: Class>(class: usize, binder: T) -> IBinder {
     unsafe {
         let class = class as *mut AIBinder_Class;
 
@@ -115,16 +218,58 @@ pub fn _new_ibinder_with_class<T: Class>(class: usize, binder: T) -> IBinder {
     }
 }
 
+#[cfg(any(feature = "stability", feature = "calling_sid"))]
+fn configure_ibinder<T: Class>(binder: &IBinder) -> Result<(), ClassError> {
+    #[cfg(feature = "stability")]
+    if let Some(stability) = T::stability() {
+        binder.mark_stability(stability).map_err(ClassError::Stability)?;
+    }
+
+    #[cfg(feature = "calling_sid")]
+    if T::requesting_sid() {
+        binder.set_requesting_sid(true).map_err(ClassError::RequestingSid)?;
+    }
+
+    Ok(())
+}
+
+#[doc(hidden)]
+pub fn _new_ibinder_with_class<T: Class>(class: usize, binder: T) -> IBinder {
+    let binder = new_ibinder(class, binder);
+
+    #[cfg(any(feature = "stability", feature = "calling_sid"))]
+    if let Err(err) = configure_ibinder::<T>(&binder) {
+        log::warn!("configure binder of {}: {}", T::INTERFACE_NAME, err);
+    }
+
+    binder
+}
+
+#[cfg(any(feature = "stability", feature = "calling_sid"))]
+impl IBinder {
+    pub fn try_new<T: DefinedClass>(service: T) -> Result<IBinder, ClassError> {
+        let binder = new_ibinder(T::_class(), service);
+
+        configure_ibinder::<T>(&binder)?;
+
+        Ok(binder)
+    }
+}
+
 #[macro_export]
 macro_rules! define_class {
     ($class:ty) => {
-        impl ::std::convert::Into<$crate::IBinder> for $class {
-            fn into(self) -> $crate::IBinder {
+        unsafe impl $crate::DefinedClass for $class {
+            fn _class() -> usize {
                 static CLASS: ::std::sync::OnceLock<usize> = ::std::sync::OnceLock::new();
 
-                let class = CLASS.get_or_init(|| $crate::_define_class_impl::<$class>());
+                *CLASS.get_or_init(|| $crate::_define_class_impl::<$class>())
+            }
+        }
 
-                $crate::_new_ibinder_with_class(*class, self)
+        impl ::std::convert::From<$class> for $crate::IBinder {
+            fn from(value: $class) -> Self {
+                $crate::_new_ibinder_with_class(<$class as $crate::DefinedClass>::_class(), value)
             }
         }
     };
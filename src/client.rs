@@ -0,0 +1,182 @@
+use std::{sync::RwLock, time::Duration};
+
+use crate::{binder::read_status_header, Flags, IBinder, Parcel, Status};
+#[cfg(feature = "service_manager")]
+use crate::{Code, ServiceManager};
+
+pub struct Call<'a> {
+    binder: &'a RwLock<IBinder>,
+    code: u32,
+    data: &'a dyn Fn(&mut Parcel) -> Result<(), Status>,
+    flags: Flags,
+    timeout: Option<Duration>,
+    interceptors: &'a [Box<dyn Interceptor>],
+}
+
+impl<'a> Call<'a> {
+    pub fn binder(&self) -> IBinder {
+        self.binder.read().unwrap().clone()
+    }
+
+    pub fn rebind(&self, binder: IBinder) {
+        *self.binder.write().unwrap() = binder;
+    }
+
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    pub fn write_data(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        (self.data)(parcel)
+    }
+
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    pub fn is_oneway(&self) -> bool {
+        self.flags.contains(Flags::ONEWAY)
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn proceed(&self) -> Result<Option<Parcel>, Status> {
+        match self.interceptors.split_first() {
+            Some((interceptor, rest)) => interceptor.intercept(&Call {
+                interceptors: rest,
+                ..*self
+            }),
+            None => match self.timeout {
+                Some(timeout) => {
+                    let binder = self.binder();
+                    let data = binder.prepare_transaction(self.data)?;
+
+                    binder.transact_prepared_with_timeout(self.code, data, self.flags, timeout)
+                }
+                None => self.binder().transact_raw(self.code, self.data, self.flags),
+            },
+        }
+    }
+}
+
+pub trait Interceptor: Send + Sync {
+    fn intercept(&self, call: &Call<'_>) -> Result<Option<Parcel>, Status>;
+}
+
+impl<F> Interceptor for F
+where
+    F: Fn(&Call<'_>) -> Result<Option<Parcel>, Status> + Send + Sync,
+{
+    fn intercept(&self, call: &Call<'_>) -> Result<Option<Parcel>, Status> {
+        self(call)
+    }
+}
+
+#[cfg(feature = "service_manager")]
+pub struct ReconnectOnDeadObject {
+    instance: String,
+    attempts: usize,
+}
+
+#[cfg(feature = "service_manager")]
+impl ReconnectOnDeadObject {
+    pub fn new(instance: impl Into<String>, attempts: usize) -> Self {
+        ReconnectOnDeadObject {
+            instance: instance.into(),
+            attempts,
+        }
+    }
+}
+
+#[cfg(feature = "service_manager")]
+impl Interceptor for ReconnectOnDeadObject {
+    fn intercept(&self, call: &Call<'_>) -> Result<Option<Parcel>, Status> {
+        let mut result = call.proceed();
+
+        for _ in 0..self.attempts {
+            match &result {
+                Err(err) if err.get_code() == Code::DeadObject => {}
+                _ => break,
+            }
+
+            match ServiceManager::get_service(&self.instance) {
+                Ok(Some(binder)) => call.rebind(binder),
+                _ => break,
+            }
+
+            result = call.proceed();
+        }
+
+        result
+    }
+}
+
+pub struct BinderClient {
+    binder: RwLock<IBinder>,
+    timeout: Option<Duration>,
+    interceptors: Vec<Box<dyn Interceptor>>,
+}
+
+impl BinderClient {
+    pub fn new(binder: IBinder) -> Self {
+        BinderClient {
+            binder: RwLock::new(binder),
+            timeout: None,
+            interceptors: Vec::new(),
+        }
+    }
+
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// A transaction that times out is not cancelled, see [`IBinder::transact_with_timeout`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn binder(&self) -> IBinder {
+        self.binder.read().unwrap().clone()
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// `data` runs once for every attempt of the interceptors.
+    pub fn transact<O, D, R>(&self, code: u32, data: D, reply: R, flags: Flags) -> Result<O, Status>
+    where
+        D: Fn(&mut Parcel) -> Result<(), Status>,
+        R: FnOnce(Option<&Parcel>) -> Result<O, Status>,
+    {
+        let reply_parcel = Call {
+            binder: &self.binder,
+            code,
+            data: &data,
+            flags,
+            timeout: self.timeout,
+            interceptors: &self.interceptors,
+        }
+        .proceed()?;
+
+        reply(reply_parcel.as_ref())
+    }
+
+    pub fn transact_with_status_header<O, D, R>(&self, code: u32, data: D, reply: R, flags: Flags) -> Result<O, Status>
+    where
+        D: Fn(&mut Parcel) -> Result<(), Status>,
+        R: FnOnce(Option<&Parcel>) -> Result<O, Status>,
+    {
+        self.transact(code, data, read_status_header(reply, flags), flags)
+    }
+}
+
+impl From<IBinder> for BinderClient {
+    fn from(value: IBinder) -> Self {
+        BinderClient::new(value)
+    }
+}
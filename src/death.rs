@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{mpsc, Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use crate::{Code, DeathRecipient, IBinder, LinkedDeathRecipient, Status};
+
+type Subscriber = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct State {
+    dead: bool,
+    next_id: usize,
+    subscribers: HashMap<usize, Subscriber>,
+}
+
+struct Notifier(Arc<Mutex<State>>);
+
+impl DeathRecipient for Notifier {
+    fn on_dead(self) {
+        let subscribers = {
+            let mut state = self.0.lock().unwrap();
+
+            state.dead = true;
+
+            std::mem::take(&mut state.subscribers)
+        };
+
+        for (_, subscriber) in subscribers {
+            subscriber();
+        }
+    }
+}
+
+pub struct DeathWatcher {
+    state: Arc<Mutex<State>>,
+    link: LinkedDeathRecipient<IBinder, Notifier>,
+}
+
+impl DeathWatcher {
+    pub fn new(binder: IBinder) -> Result<Self, Status> {
+        let state = Arc::new(Mutex::new(State::default()));
+        let link = IBinder::link_to_death(binder, Notifier(state.clone()))?;
+
+        Ok(DeathWatcher { state, link })
+    }
+
+    pub fn binder(&self) -> &IBinder {
+        self.link.binder()
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.state.lock().unwrap().dead
+    }
+
+    pub fn subscribe(&self, subscriber: impl FnOnce() + Send + 'static) -> Option<usize> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.dead {
+            drop(state);
+
+            subscriber();
+
+            return None;
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.subscribers.insert(id, Box::new(subscriber));
+
+        Some(id)
+    }
+
+    pub fn unsubscribe(&self, id: usize) -> bool {
+        self.state.lock().unwrap().subscribers.remove(&id).is_some()
+    }
+
+    pub fn channel(&self) -> mpsc::Receiver<()> {
+        let (tx, rx) = mpsc::channel();
+
+        self.subscribe(move || {
+            let _ = tx.send(());
+        });
+
+        rx
+    }
+
+    /// Resolves with `InvalidOperation` if the watcher is dropped before the binder died.
+    pub fn dead(&self) -> Death {
+        let shared = Arc::new(Mutex::new(Signal::default()));
+
+        let completion = Completion(shared.clone());
+        self.subscribe(move || completion.complete(true));
+
+        Death { shared }
+    }
+}
+
+impl Drop for DeathWatcher {
+    fn drop(&mut self) {
+        let subscribers = std::mem::take(&mut self.state.lock().unwrap().subscribers);
+
+        drop(subscribers);
+    }
+}
+
+#[derive(Default)]
+struct Signal {
+    dead: Option<bool>,
+    waker: Option<Waker>,
+}
+
+struct Completion(Arc<Mutex<Signal>>);
+
+impl Completion {
+    fn complete(&self, dead: bool) {
+        let mut signal = self.0.lock().unwrap();
+
+        if signal.dead.is_none() {
+            signal.dead = Some(dead);
+            if let Some(waker) = signal.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl Drop for Completion {
+    fn drop(&mut self) {
+        self.complete(false);
+    }
+}
+
+pub struct Death {
+    shared: Arc<Mutex<Signal>>,
+}
+
+impl Future for Death {
+    type Output = Result<(), Status>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+
+        match shared.dead {
+            Some(true) => Poll::Ready(Ok(())),
+            Some(false) => Poll::Ready(Err(Status::with_code(Code::InvalidOperation))),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,605 @@
+use std::{
+    cell::Cell,
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    ptr::{null, null_mut},
+    sync::{
+        atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use super::{
+    binder_flags_t, binder_status_t,
+    parcel::{AParcel, AParcel_delete, AParcel_markSensitive},
+    transaction_code_t, AIBinder_Class_onCreate, AIBinder_Class_onDestroy, AIBinder_Class_onTransact,
+    AIBinder_DeathRecipient_onBinderDied, AIBinder_DeathRecipient_onBinderUnlinked, AIBinder_onDump, FIRST_CALL_TRANSACTION,
+    FLAG_CLEAR_BUF, FLAG_ONEWAY, LAST_CALL_TRANSACTION, STATUS_BAD_TYPE, STATUS_DEAD_OBJECT, STATUS_INVALID_OPERATION,
+    STATUS_NAME_NOT_FOUND, STATUS_OK, STATUS_UNEXPECTED_NULL, STATUS_UNKNOWN_TRANSACTION,
+};
+
+const fn pack_chars(c1: u8, c2: u8, c3: u8, c4: u8) -> u32 {
+    ((c1 as u32) << 24) | ((c2 as u32) << 16) | ((c3 as u32) << 8) | (c4 as u32)
+}
+
+const PING_TRANSACTION: u32 = pack_chars(b'_', b'P', b'N', b'G');
+const INTERFACE_TRANSACTION: u32 = pack_chars(b'_', b'N', b'T', b'F');
+
+pub struct AIBinder_Class {
+    descriptor: CString,
+    on_create: AIBinder_Class_onCreate,
+    on_destroy: AIBinder_Class_onDestroy,
+    on_transact: AIBinder_Class_onTransact,
+    on_dump: AIBinder_onDump,
+    write_header: bool,
+}
+
+enum Kind {
+    Local {
+        class: *const AIBinder_Class,
+        user_data: *mut c_void,
+    },
+    Remote {
+        target: *mut AIBinder,
+        class: Mutex<usize>,
+        alive: AtomicBool,
+    },
+}
+
+pub struct AIBinder {
+    strong: AtomicUsize,
+    kind: Kind,
+    extension: Mutex<usize>,
+    stability: AtomicI32,
+    requesting_sid: AtomicBool,
+}
+
+unsafe impl Send for AIBinder {}
+
+unsafe impl Sync for AIBinder {}
+
+pub struct AIBinder_Weak {
+    binder: *const AIBinder,
+}
+
+pub struct AIBinder_DeathRecipient {
+    on_died: AIBinder_DeathRecipient_onBinderDied,
+    on_unlinked: Mutex<AIBinder_DeathRecipient_onBinderUnlinked>,
+}
+
+struct Link {
+    binder: usize,
+    recipient: usize,
+    cookie: usize,
+}
+
+static LINKS: Mutex<Vec<Link>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static TRANSACTION_DEPTH: Cell<usize> = const { Cell::new(0) };
+    static TRANSACTION_SID: Cell<*const c_char> = const { Cell::new(null()) };
+}
+
+pub const CALLING_SID: &CStr = c"u:r:fake_app:s0";
+
+impl AIBinder {
+    fn new(kind: Kind) -> *mut AIBinder {
+        Arc::into_raw(Arc::new(AIBinder {
+            strong: AtomicUsize::new(1),
+            kind,
+            extension: Mutex::new(0),
+            stability: AtomicI32::new(STABILITY_UNDECLARED),
+            requesting_sid: AtomicBool::new(false),
+        }))
+        .cast_mut()
+    }
+
+    fn class(&self) -> *const AIBinder_Class {
+        match &self.kind {
+            Kind::Local { class, .. } => *class,
+            Kind::Remote { class, .. } => *class.lock().unwrap() as *const AIBinder_Class,
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        match &self.kind {
+            Kind::Local { .. } => true,
+            Kind::Remote { alive, .. } => alive.load(Ordering::SeqCst),
+        }
+    }
+
+    fn local(&self) -> *mut AIBinder {
+        match &self.kind {
+            Kind::Local { .. } => self as *const AIBinder as *mut AIBinder,
+            Kind::Remote { target, .. } => *target,
+        }
+    }
+}
+
+fn take_links(filter: impl Fn(&Link) -> bool) -> Vec<Link> {
+    let mut links = LINKS.lock().unwrap();
+
+    let (taken, kept) = std::mem::take(&mut *links).into_iter().partition(filter);
+    *links = kept;
+
+    taken
+}
+
+unsafe fn notify_unlinked(link: &Link) {
+    let recipient = &*(link.recipient as *const AIBinder_DeathRecipient);
+
+    if let Some(on_unlinked) = *recipient.on_unlinked.lock().unwrap() {
+        on_unlinked(link.cookie as *mut c_void);
+    }
+}
+
+pub fn new_remote(target: *mut AIBinder) -> *mut AIBinder {
+    unsafe {
+        AIBinder_incStrong(target);
+    }
+
+    AIBinder::new(Kind::Remote {
+        target,
+        class: Mutex::new(0),
+        alive: AtomicBool::new(true),
+    })
+}
+
+pub const STABILITY_UNDECLARED: i32 = 0;
+pub const STABILITY_VENDOR: i32 = 0b000011;
+pub const STABILITY_SYSTEM: i32 = 0b001100;
+pub const STABILITY_VINTF: i32 = 0b111111;
+pub const STABILITY_LOCAL: i32 = STABILITY_SYSTEM;
+
+pub fn stability(binder: *mut AIBinder) -> i32 {
+    unsafe { (*(*binder).local()).stability.load(Ordering::SeqCst) }
+}
+
+unsafe fn mark_stability(binder: *mut AIBinder, stability: i32) {
+    if !matches!((*binder).kind, Kind::Local { .. }) {
+        panic!("marking stability of a remote binder");
+    }
+
+    (*binder).stability.store(stability, Ordering::SeqCst);
+}
+
+pub unsafe extern "C" fn AIBinder_markVendorStability(binder: *mut AIBinder) {
+    mark_stability(binder, STABILITY_VENDOR)
+}
+
+pub unsafe extern "C" fn AIBinder_markSystemStability(binder: *mut AIBinder) {
+    mark_stability(binder, STABILITY_SYSTEM)
+}
+
+pub unsafe extern "C" fn AIBinder_markVintfStability(binder: *mut AIBinder) {
+    mark_stability(binder, STABILITY_VINTF)
+}
+
+pub unsafe extern "C" fn AIBinder_forceDowngradeToLocalStability(binder: *mut AIBinder) {
+    mark_stability(binder, STABILITY_LOCAL)
+}
+
+pub fn kill_remote(binder: *mut AIBinder) {
+    unsafe {
+        if let Kind::Remote { alive, .. } = &(*binder).kind {
+            if !alive.swap(false, Ordering::SeqCst) {
+                return;
+            }
+        }
+
+        for link in take_links(|link| link.binder == binder as usize) {
+            let recipient = &*(link.recipient as *const AIBinder_DeathRecipient);
+
+            if let Some(on_died) = recipient.on_died {
+                on_died(link.cookie as *mut c_void);
+            }
+
+            notify_unlinked(&link);
+        }
+    }
+}
+
+pub unsafe extern "C" fn AIBinder_Class_define(
+    descriptor: *const c_char,
+    on_create: AIBinder_Class_onCreate,
+    on_destroy: AIBinder_Class_onDestroy,
+    on_transact: AIBinder_Class_onTransact,
+) -> *mut AIBinder_Class {
+    Box::into_raw(Box::new(AIBinder_Class {
+        descriptor: CStr::from_ptr(descriptor).to_owned(),
+        on_create,
+        on_destroy,
+        on_transact,
+        on_dump: None,
+        write_header: true,
+    }))
+}
+
+pub unsafe extern "C" fn AIBinder_Class_setOnDump(class: *mut AIBinder_Class, on_dump: AIBinder_onDump) {
+    (*class).on_dump = on_dump;
+}
+
+pub unsafe extern "C" fn AIBinder_Class_disableInterfaceTokenHeader(class: *mut AIBinder_Class) {
+    (*class).write_header = false;
+}
+
+pub unsafe extern "C" fn AIBinder_Class_getDescriptor(class: *const AIBinder_Class) -> *const c_char {
+    (*class).descriptor.as_ptr()
+}
+
+pub unsafe extern "C" fn AIBinder_new(class: *const AIBinder_Class, args: *mut c_void) -> *mut AIBinder {
+    let user_data = match (*class).on_create {
+        Some(on_create) => on_create(args),
+        None => args,
+    };
+
+    AIBinder::new(Kind::Local { class, user_data })
+}
+
+pub unsafe extern "C" fn AIBinder_incStrong(binder: *mut AIBinder) {
+    (*binder).strong.fetch_add(1, Ordering::SeqCst);
+
+    Arc::increment_strong_count(binder.cast_const());
+}
+
+pub unsafe extern "C" fn AIBinder_decStrong(binder: *mut AIBinder) {
+    if (*binder).strong.fetch_sub(1, Ordering::SeqCst) == 1 {
+        let extension = std::mem::take(&mut *(*binder).extension.lock().unwrap());
+        if extension != 0 {
+            AIBinder_decStrong(extension as *mut AIBinder);
+        }
+
+        match &(*binder).kind {
+            Kind::Local { class, user_data } => {
+                if let Some(on_destroy) = (**class).on_destroy {
+                    on_destroy(*user_data);
+                }
+            }
+            Kind::Remote { target, .. } => {
+                for link in take_links(|link| link.binder == binder as usize) {
+                    notify_unlinked(&link);
+                }
+
+                AIBinder_decStrong(*target);
+            }
+        }
+    }
+
+    Arc::decrement_strong_count(binder.cast_const());
+}
+
+pub unsafe extern "C" fn AIBinder_debugGetRefCount(binder: *mut AIBinder) -> i32 {
+    (*binder).strong.load(Ordering::SeqCst) as i32
+}
+
+pub unsafe extern "C" fn AIBinder_isRemote(binder: *const AIBinder) -> bool {
+    matches!((*binder).kind, Kind::Remote { .. })
+}
+
+pub unsafe extern "C" fn AIBinder_isAlive(binder: *const AIBinder) -> bool {
+    (*binder).is_alive()
+}
+
+pub unsafe extern "C" fn AIBinder_ping(binder: *mut AIBinder) -> binder_status_t {
+    if (*binder).is_alive() {
+        STATUS_OK
+    } else {
+        STATUS_DEAD_OBJECT
+    }
+}
+
+pub unsafe extern "C" fn AIBinder_associateClass(binder: *mut AIBinder, class: *const AIBinder_Class) -> bool {
+    if class.is_null() {
+        return false;
+    }
+
+    match &(*binder).kind {
+        Kind::Local { class: own, .. } => *own == class,
+        Kind::Remote {
+            target,
+            class: associated,
+            ..
+        } => {
+            let mut associated = associated.lock().unwrap();
+            if *associated == class as usize {
+                return true;
+            } else if *associated != 0 {
+                return false;
+            }
+
+            if (*(**target).class()).descriptor != (*class).descriptor {
+                return false;
+            }
+
+            *associated = class as usize;
+
+            true
+        }
+    }
+}
+
+pub unsafe extern "C" fn AIBinder_getClass(binder: *mut AIBinder) -> *const AIBinder_Class {
+    (*binder).class()
+}
+
+pub unsafe extern "C" fn AIBinder_getUserData(binder: *mut AIBinder) -> *mut c_void {
+    match &(*binder).kind {
+        Kind::Local { user_data, .. } => *user_data,
+        Kind::Remote { .. } => null_mut(),
+    }
+}
+
+pub unsafe extern "C" fn AIBinder_lt(lhs: *const AIBinder, rhs: *const AIBinder) -> bool {
+    lhs < rhs
+}
+
+pub unsafe extern "C" fn AIBinder_getCallingUid() -> u32 {
+    libc::getuid()
+}
+
+pub unsafe extern "C" fn AIBinder_getCallingPid() -> i32 {
+    std::process::id() as i32
+}
+
+pub unsafe extern "C" fn AIBinder_setRequestingSid(binder: *mut AIBinder, requesting_sid: bool) {
+    (*binder).requesting_sid.store(requesting_sid, Ordering::SeqCst);
+}
+
+pub unsafe extern "C" fn AIBinder_getCallingSid() -> *const c_char {
+    TRANSACTION_SID.get()
+}
+
+pub unsafe extern "C" fn AIBinder_isHandlingTransaction() -> bool {
+    TRANSACTION_DEPTH.with(|depth| depth.get() > 0)
+}
+
+pub unsafe extern "C" fn AIBinder_prepareTransaction(binder: *mut AIBinder, data: *mut *mut AParcel) -> binder_status_t {
+    let class = (*binder).class();
+    if class.is_null() {
+        return STATUS_INVALID_OPERATION;
+    }
+
+    let parcel = AParcel::new();
+    if (*class).write_header {
+        (*parcel).write_interface_token(&(*class).descriptor.to_string_lossy());
+    }
+
+    *data = parcel;
+
+    STATUS_OK
+}
+
+unsafe fn dispatch(binder: *mut AIBinder, code: transaction_code_t, data: &AParcel, reply: *mut AParcel) -> binder_status_t {
+    let class = &*(*binder).class();
+
+    match code {
+        FIRST_CALL_TRANSACTION..=LAST_CALL_TRANSACTION => {
+            if class.write_header && !data.check_interface(&class.descriptor.to_string_lossy()) {
+                return STATUS_BAD_TYPE;
+            }
+
+            let on_transact = match class.on_transact {
+                Some(on_transact) => on_transact,
+                None => return STATUS_UNKNOWN_TRANSACTION,
+            };
+
+            let sid = if (*binder).requesting_sid.load(Ordering::SeqCst) {
+                CALLING_SID.as_ptr()
+            } else {
+                null()
+            };
+
+            TRANSACTION_DEPTH.with(|depth| depth.set(depth.get() + 1));
+            let previous_sid = TRANSACTION_SID.replace(sid);
+            let status = on_transact(binder, code, data, reply);
+            TRANSACTION_SID.set(previous_sid);
+            TRANSACTION_DEPTH.with(|depth| depth.set(depth.get() - 1));
+
+            status
+        }
+        INTERFACE_TRANSACTION => {
+            (*reply).write_string16(Some(&class.descriptor.to_string_lossy()));
+
+            STATUS_OK
+        }
+        PING_TRANSACTION => STATUS_OK,
+        _ => STATUS_UNKNOWN_TRANSACTION,
+    }
+}
+
+pub unsafe extern "C" fn AIBinder_transact(
+    binder: *mut AIBinder,
+    code: transaction_code_t,
+    data: *mut *mut AParcel,
+    reply: *mut *mut AParcel,
+    flags: binder_flags_t,
+) -> binder_status_t {
+    if data.is_null() || (*data).is_null() {
+        return STATUS_UNEXPECTED_NULL;
+    }
+
+    let data_parcel = Box::from_raw(*data);
+    *data = null_mut();
+
+    if !(*binder).is_alive() {
+        return STATUS_DEAD_OBJECT;
+    }
+
+    data_parcel.rewind();
+
+    let reply_parcel = AParcel::new();
+    let status = dispatch((*binder).local(), code, &data_parcel, reply_parcel);
+    if status != STATUS_OK {
+        AParcel_delete(reply_parcel);
+
+        return status;
+    }
+
+    if flags & FLAG_ONEWAY != 0 && AIBinder_isRemote(binder) {
+        AParcel_delete(reply_parcel);
+
+        *reply = AParcel::new();
+    } else {
+        (*reply_parcel).rewind();
+
+        if flags & FLAG_CLEAR_BUF != 0 {
+            AParcel_markSensitive(reply_parcel);
+        }
+
+        *reply = reply_parcel;
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AIBinder_dump(binder: *mut AIBinder, fd: c_int, args: *mut *const c_char, len: u32) -> binder_status_t {
+    if !(*binder).is_alive() {
+        return STATUS_DEAD_OBJECT;
+    }
+
+    let local = (*binder).local();
+    match (*(*local).class()).on_dump {
+        Some(on_dump) => on_dump(local, fd, args, len),
+        None => STATUS_OK,
+    }
+}
+
+pub unsafe extern "C" fn AIBinder_getExtension(binder: *mut AIBinder, extension: *mut *mut AIBinder) -> binder_status_t {
+    let ptr = *(*(*binder).local()).extension.lock().unwrap() as *mut AIBinder;
+    if !ptr.is_null() {
+        AIBinder_incStrong(ptr);
+    }
+
+    *extension = ptr;
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AIBinder_setExtension(binder: *mut AIBinder, extension: *mut AIBinder) -> binder_status_t {
+    if AIBinder_isRemote(binder) {
+        return STATUS_INVALID_OPERATION;
+    }
+
+    AIBinder_incStrong(extension);
+
+    let old = std::mem::replace(&mut *(*binder).extension.lock().unwrap(), extension as usize);
+    if old != 0 {
+        AIBinder_decStrong(old as *mut AIBinder);
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AIBinder_Weak_new(binder: *mut AIBinder) -> *mut AIBinder_Weak {
+    Arc::increment_strong_count(binder.cast_const());
+
+    Box::into_raw(Box::new(AIBinder_Weak { binder }))
+}
+
+pub unsafe extern "C" fn AIBinder_Weak_clone(weak: *const AIBinder_Weak) -> *mut AIBinder_Weak {
+    AIBinder_Weak_new((*weak).binder.cast_mut())
+}
+
+pub unsafe extern "C" fn AIBinder_Weak_delete(weak: *mut AIBinder_Weak) {
+    let weak = Box::from_raw(weak);
+
+    Arc::decrement_strong_count(weak.binder);
+}
+
+pub unsafe extern "C" fn AIBinder_Weak_promote(weak: *mut AIBinder_Weak) -> *mut AIBinder {
+    let binder = (*weak).binder;
+
+    let promoted = (*binder).strong.fetch_update(
+        Ordering::SeqCst,
+        Ordering::SeqCst,
+        |n| if n == 0 { None } else { Some(n + 1) },
+    );
+
+    match promoted {
+        Ok(_) => {
+            Arc::increment_strong_count(binder);
+
+            binder.cast_mut()
+        }
+        Err(_) => null_mut(),
+    }
+}
+
+pub unsafe extern "C" fn AIBinder_Weak_lt(lhs: *const AIBinder_Weak, rhs: *const AIBinder_Weak) -> bool {
+    (*lhs).binder < (*rhs).binder
+}
+
+pub unsafe extern "C" fn AIBinder_DeathRecipient_new(
+    on_died: AIBinder_DeathRecipient_onBinderDied,
+) -> *mut AIBinder_DeathRecipient {
+    Box::into_raw(Box::new(AIBinder_DeathRecipient {
+        on_died,
+        on_unlinked: Mutex::new(None),
+    }))
+}
+
+pub unsafe extern "C" fn AIBinder_DeathRecipient_setOnUnlinked(
+    recipient: *mut AIBinder_DeathRecipient,
+    on_unlinked: AIBinder_DeathRecipient_onBinderUnlinked,
+) {
+    *(*recipient).on_unlinked.lock().unwrap() = on_unlinked;
+}
+
+pub unsafe extern "C" fn AIBinder_DeathRecipient_delete(recipient: *mut AIBinder_DeathRecipient) {
+    for link in take_links(|link| link.recipient == recipient as usize) {
+        notify_unlinked(&link);
+    }
+
+    drop(Box::from_raw(recipient))
+}
+
+pub unsafe extern "C" fn AIBinder_linkToDeath(
+    binder: *mut AIBinder,
+    recipient: *mut AIBinder_DeathRecipient,
+    cookie: *mut c_void,
+) -> binder_status_t {
+    let link = Link {
+        binder: binder as usize,
+        recipient: recipient as usize,
+        cookie: cookie as usize,
+    };
+
+    let status = if !AIBinder_isRemote(binder) {
+        STATUS_INVALID_OPERATION
+    } else if !(*binder).is_alive() {
+        STATUS_DEAD_OBJECT
+    } else {
+        LINKS.lock().unwrap().push(link);
+
+        return STATUS_OK;
+    };
+
+    // libbinder_ndk releases the failed link right away, which reports it as unlinked
+    notify_unlinked(&link);
+
+    status
+}
+
+pub unsafe extern "C" fn AIBinder_unlinkToDeath(
+    binder: *mut AIBinder,
+    recipient: *mut AIBinder_DeathRecipient,
+    cookie: *mut c_void,
+) -> binder_status_t {
+    let links = {
+        let mut links = LINKS.lock().unwrap();
+
+        let idx = links
+            .iter()
+            .position(|l| l.binder == binder as usize && l.recipient == recipient as usize && l.cookie == cookie as usize);
+
+        idx.map(|idx| links.remove(idx))
+    };
+
+    match links {
+        Some(link) => {
+            notify_unlinked(&link);
+
+            STATUS_OK
+        }
+        None => STATUS_NAME_NOT_FOUND,
+    }
+}
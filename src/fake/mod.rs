@@ -0,0 +1,107 @@
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{c_char, c_int, c_void, CStr};
+
+pub use binder::*;
+pub use parcel::*;
+pub use process::*;
+pub use status::*;
+
+mod binder;
+mod parcel;
+mod process;
+mod status;
+
+pub type binder_status_t = i32;
+pub type binder_exception_t = i32;
+pub type transaction_code_t = u32;
+pub type binder_flags_t = u32;
+
+pub const FLAG_ONEWAY: u32 = 0x01;
+pub const FLAG_CLEAR_BUF: u32 = 0x20;
+pub const FIRST_CALL_TRANSACTION: u32 = 0x00000001;
+pub const LAST_CALL_TRANSACTION: u32 = 0x00ffffff;
+
+pub const STATUS_OK: binder_status_t = 0;
+pub const STATUS_UNKNOWN_ERROR: binder_status_t = i32::MIN;
+pub const STATUS_NO_MEMORY: binder_status_t = -12;
+pub const STATUS_INVALID_OPERATION: binder_status_t = -38;
+pub const STATUS_BAD_VALUE: binder_status_t = -22;
+pub const STATUS_BAD_TYPE: binder_status_t = STATUS_UNKNOWN_ERROR + 1;
+pub const STATUS_NAME_NOT_FOUND: binder_status_t = -2;
+pub const STATUS_PERMISSION_DENIED: binder_status_t = -1;
+pub const STATUS_NO_INIT: binder_status_t = -19;
+pub const STATUS_ALREADY_EXISTS: binder_status_t = -17;
+pub const STATUS_DEAD_OBJECT: binder_status_t = -32;
+pub const STATUS_FAILED_TRANSACTION: binder_status_t = STATUS_UNKNOWN_ERROR + 2;
+pub const STATUS_BAD_INDEX: binder_status_t = -75;
+pub const STATUS_NOT_ENOUGH_DATA: binder_status_t = -61;
+pub const STATUS_WOULD_BLOCK: binder_status_t = -11;
+pub const STATUS_TIMED_OUT: binder_status_t = -110;
+pub const STATUS_UNKNOWN_TRANSACTION: binder_status_t = -74;
+pub const STATUS_FDS_NOT_ALLOWED: binder_status_t = STATUS_UNKNOWN_ERROR + 7;
+pub const STATUS_UNEXPECTED_NULL: binder_status_t = STATUS_UNKNOWN_ERROR + 8;
+
+pub const EX_NONE: binder_exception_t = 0;
+pub const EX_SECURITY: binder_exception_t = -1;
+pub const EX_BAD_PARCELABLE: binder_exception_t = -2;
+pub const EX_ILLEGAL_ARGUMENT: binder_exception_t = -3;
+pub const EX_NULL_POINTER: binder_exception_t = -4;
+pub const EX_ILLEGAL_STATE: binder_exception_t = -5;
+pub const EX_NETWORK_MAIN_THREAD: binder_exception_t = -6;
+pub const EX_UNSUPPORTED_OPERATION: binder_exception_t = -7;
+pub const EX_SERVICE_SPECIFIC: binder_exception_t = -8;
+pub const EX_PARCELABLE: binder_exception_t = -9;
+pub const EX_TRANSACTION_FAILED: binder_exception_t = -129;
+
+pub type AIBinder_Class_onCreate = Option<unsafe extern "C" fn(args: *mut c_void) -> *mut c_void>;
+pub type AIBinder_Class_onDestroy = Option<unsafe extern "C" fn(user_data: *mut c_void)>;
+pub type AIBinder_Class_onTransact = Option<
+    unsafe extern "C" fn(
+        binder: *mut AIBinder,
+        code: transaction_code_t,
+        data: *const AParcel,
+        reply: *mut AParcel,
+    ) -> binder_status_t,
+>;
+pub type AIBinder_onDump =
+    Option<unsafe extern "C" fn(binder: *mut AIBinder, fd: c_int, args: *mut *const c_char, len: u32) -> binder_status_t>;
+pub type AIBinder_DeathRecipient_onBinderDied = Option<unsafe extern "C" fn(cookie: *mut c_void)>;
+pub type AIBinder_DeathRecipient_onBinderUnlinked = Option<unsafe extern "C" fn(cookie: *mut c_void)>;
+
+pub type AParcel_boolArrayAllocator = Option<unsafe extern "C" fn(data: *mut c_void, length: i32) -> bool>;
+pub type AParcel_boolArraySetter = Option<unsafe extern "C" fn(data: *mut c_void, index: usize, value: bool)>;
+pub type AParcel_boolArrayGetter = Option<unsafe extern "C" fn(data: *const c_void, index: usize) -> bool>;
+pub type AParcel_stringAllocator = Option<unsafe extern "C" fn(data: *mut c_void, length: i32, buffer: *mut *mut c_char) -> bool>;
+pub type AParcel_stringArrayAllocator = Option<unsafe extern "C" fn(data: *mut c_void, length: i32) -> bool>;
+pub type AParcel_stringArrayElementAllocator =
+    Option<unsafe extern "C" fn(data: *mut c_void, index: usize, length: i32, buffer: *mut *mut c_char) -> bool>;
+pub type AParcel_stringArrayElementGetter =
+    Option<unsafe extern "C" fn(data: *const c_void, index: usize, length: *mut i32) -> *const c_char>;
+pub type AParcel_parcelableArrayAllocator = Option<unsafe extern "C" fn(data: *mut c_void, length: i32) -> bool>;
+pub type AParcel_readParcelableElement =
+    Option<unsafe extern "C" fn(parcel: *const AParcel, data: *mut c_void, index: usize) -> binder_status_t>;
+pub type AParcel_writeParcelableElement =
+    Option<unsafe extern "C" fn(parcel: *mut AParcel, data: *const c_void, index: usize) -> binder_status_t>;
+
+pub unsafe fn dlsym(name: *const c_char) -> *mut c_void {
+    match CStr::from_ptr(name).to_bytes() {
+        b"AIBinder_markVendorStability" => AIBinder_markVendorStability as *mut c_void,
+        b"AIBinder_markSystemStability" => AIBinder_markSystemStability as *mut c_void,
+        b"AIBinder_markVintfStability" => AIBinder_markVintfStability as *mut c_void,
+        b"AIBinder_forceDowngradeToLocalStability" => AIBinder_forceDowngradeToLocalStability as *mut c_void,
+        b"AIBinder_setRequestingSid" => AIBinder_setRequestingSid as *mut c_void,
+        b"AIBinder_getCallingSid" => AIBinder_getCallingSid as *mut c_void,
+        b"AParcel_markSensitive" => AParcel_markSensitive as *mut c_void,
+        b"ABinderProcess_startThreadPool" => ABinderProcess_startThreadPool as *mut c_void,
+        b"ABinderProcess_setThreadPoolMaxThreadCount" => ABinderProcess_setThreadPoolMaxThreadCount as *mut c_void,
+        b"ABinderProcess_joinThreadPool" => ABinderProcess_joinThreadPool as *mut c_void,
+        b"ABinderProcess_setupPolling" => ABinderProcess_setupPolling as *mut c_void,
+        b"ABinderProcess_handlePolledCommands" => ABinderProcess_handlePolledCommands as *mut c_void,
+        _ => std::ptr::null_mut(),
+    }
+}
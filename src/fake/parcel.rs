@@ -0,0 +1,772 @@
+use std::{
+    cell::Cell,
+    ffi::{c_char, c_int, c_void, CString},
+    os::fd::{BorrowedFd, IntoRawFd, OwnedFd},
+    ptr::null_mut,
+};
+
+use super::{
+    binder::{AIBinder_decStrong, AIBinder_incStrong},
+    binder_status_t,
+    status::AStatus,
+    AIBinder, AParcel_boolArrayAllocator, AParcel_boolArrayGetter, AParcel_boolArraySetter, AParcel_parcelableArrayAllocator,
+    AParcel_readParcelableElement, AParcel_stringAllocator, AParcel_stringArrayAllocator, AParcel_stringArrayElementAllocator,
+    AParcel_stringArrayElementGetter, AParcel_writeParcelableElement, EX_NONE, EX_PARCELABLE, EX_SERVICE_SPECIFIC,
+    EX_TRANSACTION_FAILED, STATUS_BAD_TYPE, STATUS_BAD_VALUE, STATUS_INVALID_OPERATION, STATUS_NOT_ENOUGH_DATA, STATUS_NO_MEMORY,
+    STATUS_OK, STATUS_UNEXPECTED_NULL,
+};
+
+const EX_HAS_REPLY_HEADER: i32 = -128;
+const INTERFACE_HEADER: i32 = i32::from_be_bytes(*b"SYST");
+
+enum Object {
+    Binder(*mut AIBinder),
+    Fd(OwnedFd),
+}
+
+impl Drop for Object {
+    fn drop(&mut self) {
+        if let Object::Binder(ptr) = *self {
+            if !ptr.is_null() {
+                unsafe { AIBinder_decStrong(ptr) }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct AParcel {
+    data: Vec<u8>,
+    position: Cell<usize>,
+    objects: Vec<(usize, Object)>,
+    sensitive: Cell<bool>,
+}
+
+const fn pad(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+type Result<T> = std::result::Result<T, binder_status_t>;
+
+impl AParcel {
+    pub(super) fn new() -> *mut AParcel {
+        Box::into_raw(Box::default())
+    }
+
+    pub(super) fn rewind(&self) {
+        self.position.set(0)
+    }
+
+    fn write_raw(&mut self, bytes: &[u8]) {
+        let start = self.position.get();
+        let end = start + pad(bytes.len());
+
+        if self.data.len() < end {
+            self.data.resize(end, 0);
+        }
+
+        self.data[start..start + bytes.len()].copy_from_slice(bytes);
+        self.data[start + bytes.len()..end].fill(0);
+        self.objects.retain(|(offset, _)| *offset < start || *offset >= end);
+        self.position.set(end);
+    }
+
+    fn read_raw(&self, len: usize) -> Result<&[u8]> {
+        let start = self.position.get();
+        let end = start.checked_add(pad(len)).ok_or(STATUS_BAD_VALUE)?;
+
+        if end > self.data.len() {
+            return Err(STATUS_NOT_ENOUGH_DATA);
+        }
+
+        self.position.set(end);
+
+        Ok(&self.data[start..start + len])
+    }
+
+    pub(super) fn write_i32(&mut self, value: i32) {
+        self.write_raw(&value.to_le_bytes())
+    }
+
+    pub(super) fn read_i32(&self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.read_raw(4)?.try_into().unwrap()))
+    }
+
+    fn write_object(&mut self, object: Object) {
+        let offset = self.position.get();
+
+        self.write_raw(&[0; 8]);
+        self.objects.push((offset, object));
+    }
+
+    fn read_object(&self) -> Result<&Object> {
+        let offset = self.position.get();
+        let object = self
+            .objects
+            .iter()
+            .find(|(o, _)| *o == offset)
+            .map(|(_, object)| object)
+            .ok_or(STATUS_BAD_TYPE)?;
+
+        self.read_raw(8)?;
+
+        Ok(object)
+    }
+
+    pub(super) fn write_string16(&mut self, value: Option<&str>) {
+        match value {
+            None => self.write_i32(-1),
+            Some(s) => {
+                let units = s.encode_utf16().chain([0]).flat_map(u16::to_le_bytes).collect::<Vec<_>>();
+
+                self.write_i32((units.len() / 2 - 1) as i32);
+                self.write_raw(&units);
+            }
+        }
+    }
+
+    pub(super) fn read_string16(&self) -> Result<Option<String>> {
+        let len = self.read_i32()?;
+        if len == -1 {
+            return Ok(None);
+        } else if len < -1 {
+            return Err(STATUS_BAD_VALUE);
+        }
+
+        let bytes = self.read_raw((len as usize + 1) * 2)?;
+        let units = bytes.chunks(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect::<Vec<_>>();
+        if units[len as usize] != 0 {
+            return Err(STATUS_BAD_VALUE);
+        }
+
+        String::from_utf16(&units[..len as usize])
+            .map(Some)
+            .map_err(|_| STATUS_BAD_VALUE)
+    }
+
+    pub(super) fn write_interface_token(&mut self, descriptor: &str) {
+        self.write_i32(0);
+        self.write_i32(-1);
+        self.write_i32(INTERFACE_HEADER);
+        self.write_string16(Some(descriptor));
+    }
+
+    pub(super) fn check_interface(&self, descriptor: &str) -> bool {
+        let header =
+            (|| Ok::<_, binder_status_t>((self.read_i32()?, self.read_i32()?, self.read_i32()?, self.read_string16()?)))();
+
+        matches!(header, Ok((_, _, INTERFACE_HEADER, Some(d))) if d == descriptor)
+    }
+}
+
+fn status(result: Result<()>) -> binder_status_t {
+    match result {
+        Ok(_) => STATUS_OK,
+        Err(err) => err,
+    }
+}
+
+pub unsafe extern "C" fn AParcel_create() -> *mut AParcel {
+    AParcel::new()
+}
+
+pub unsafe extern "C" fn AParcel_delete(parcel: *mut AParcel) {
+    if !parcel.is_null() {
+        drop(Box::from_raw(parcel))
+    }
+}
+
+pub unsafe extern "C" fn AParcel_markSensitive(parcel: *const AParcel) {
+    (*parcel).sensitive.set(true)
+}
+
+pub unsafe fn is_sensitive(parcel: *const AParcel) -> bool {
+    (*parcel).sensitive.get()
+}
+
+pub unsafe extern "C" fn AParcel_reset(parcel: *mut AParcel) -> binder_status_t {
+    *parcel = AParcel::default();
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_getDataSize(parcel: *const AParcel) -> i32 {
+    (*parcel).data.len() as i32
+}
+
+pub unsafe extern "C" fn AParcel_getDataPosition(parcel: *const AParcel) -> i32 {
+    (*parcel).position.get() as i32
+}
+
+pub unsafe extern "C" fn AParcel_setDataPosition(parcel: *const AParcel, position: i32) -> binder_status_t {
+    if position < 0 {
+        return STATUS_BAD_VALUE;
+    }
+
+    (*parcel).position.set(position as usize);
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_appendFrom(from: *const AParcel, to: *mut AParcel, start: i32, size: i32) -> binder_status_t {
+    let (from, to) = (&*from, &mut *to);
+    if start < 0 || size < 0 || start as usize + size as usize > from.data.len() {
+        return STATUS_BAD_VALUE;
+    }
+
+    let (start, end) = (start as usize, start as usize + size as usize);
+    let base = to.position.get();
+
+    to.write_raw(&from.data[start..end]);
+    for (offset, object) in &from.objects {
+        if (start..end).contains(offset) {
+            let object = match object {
+                Object::Binder(ptr) => {
+                    if !ptr.is_null() {
+                        AIBinder_incStrong(*ptr);
+                    }
+
+                    Object::Binder(*ptr)
+                }
+                Object::Fd(fd) => match fd.try_clone() {
+                    Ok(fd) => Object::Fd(fd),
+                    Err(_) => return STATUS_NO_MEMORY,
+                },
+            };
+
+            to.objects.push((base + offset - start, object));
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_marshal(parcel: *const AParcel, buffer: *mut u8, start: usize, len: usize) -> binder_status_t {
+    let parcel = &*parcel;
+    if !parcel.objects.is_empty() {
+        return STATUS_INVALID_OPERATION;
+    }
+    if start.checked_add(len).is_none_or(|end| end > parcel.data.len()) {
+        return STATUS_BAD_VALUE;
+    }
+
+    std::ptr::copy_nonoverlapping(parcel.data.as_ptr().add(start), buffer, len);
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_unmarshal(parcel: *mut AParcel, buffer: *const u8, len: usize) -> binder_status_t {
+    *parcel = AParcel {
+        data: std::slice::from_raw_parts(buffer, len).to_vec(),
+        ..AParcel::default()
+    };
+
+    STATUS_OK
+}
+
+macro_rules! basic_type {
+    ($typ:ty, $write:ident, $read:ident) => {
+        pub unsafe extern "C" fn $write(parcel: *mut AParcel, value: $typ) -> binder_status_t {
+            (*parcel).write_raw(&value.to_le_bytes());
+
+            STATUS_OK
+        }
+
+        pub unsafe extern "C" fn $read(parcel: *const AParcel, value: *mut $typ) -> binder_status_t {
+            status((*parcel).read_raw(std::mem::size_of::<$typ>()).map(|bytes| {
+                *value = <$typ>::from_le_bytes(bytes.try_into().unwrap());
+            }))
+        }
+    };
+}
+
+basic_type!(f64, AParcel_writeDouble, AParcel_readDouble);
+basic_type!(f32, AParcel_writeFloat, AParcel_readFloat);
+basic_type!(i32, AParcel_writeInt32, AParcel_readInt32);
+basic_type!(i64, AParcel_writeInt64, AParcel_readInt64);
+basic_type!(u32, AParcel_writeUint32, AParcel_readUint32);
+basic_type!(u64, AParcel_writeUint64, AParcel_readUint64);
+
+macro_rules! widened_type {
+    ($typ:ty, $write:ident, $read:ident, $from:expr, $into:expr) => {
+        pub unsafe extern "C" fn $write(parcel: *mut AParcel, value: $typ) -> binder_status_t {
+            (*parcel).write_i32($into(value));
+
+            STATUS_OK
+        }
+
+        pub unsafe extern "C" fn $read(parcel: *const AParcel, value: *mut $typ) -> binder_status_t {
+            status((*parcel).read_i32().map(|v| *value = $from(v)))
+        }
+    };
+}
+
+widened_type!(bool, AParcel_writeBool, AParcel_readBool, |v: i32| v != 0, |v: bool| v as i32);
+widened_type!(i8, AParcel_writeByte, AParcel_readByte, |v: i32| v as i8, |v: i8| v as i32);
+widened_type!(u16, AParcel_writeChar, AParcel_readChar, |v: i32| v as u16, |v: u16| v as i32);
+
+macro_rules! packed_array {
+    ($typ:ty, $write:ident, $read:ident) => {
+        pub unsafe extern "C" fn $write(parcel: *mut AParcel, array: *const $typ, length: i32) -> binder_status_t {
+            let parcel = &mut *parcel;
+            if length < 0 {
+                parcel.write_i32(-1);
+
+                return STATUS_OK;
+            }
+            if array.is_null() && length > 0 {
+                return STATUS_UNEXPECTED_NULL;
+            }
+
+            parcel.write_i32(length);
+            if length > 0 {
+                let array = std::slice::from_raw_parts(array, length as usize);
+                parcel.write_raw(&array.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>());
+            }
+
+            STATUS_OK
+        }
+
+        pub unsafe extern "C" fn $read(
+            parcel: *const AParcel,
+            data: *mut c_void,
+            allocator: Option<unsafe extern "C" fn(*mut c_void, i32, *mut *mut $typ) -> bool>,
+        ) -> binder_status_t {
+            let parcel = &*parcel;
+            let length = match parcel.read_i32() {
+                Ok(length) => length,
+                Err(err) => return err,
+            };
+            if length < -1 {
+                return STATUS_BAD_VALUE;
+            }
+
+            let mut array: *mut $typ = null_mut();
+            if !allocator.unwrap()(data, length, &mut array) {
+                return STATUS_NO_MEMORY;
+            }
+            if length <= 0 {
+                return STATUS_OK;
+            }
+            if array.is_null() {
+                return STATUS_NO_MEMORY;
+            }
+
+            const SIZE: usize = std::mem::size_of::<$typ>();
+
+            match parcel.read_raw(SIZE * length as usize) {
+                Ok(bytes) => {
+                    for (idx, chunk) in bytes.chunks(SIZE).enumerate() {
+                        *array.add(idx) = <$typ>::from_le_bytes(chunk.try_into().unwrap());
+                    }
+
+                    STATUS_OK
+                }
+                Err(err) => err,
+            }
+        }
+    };
+}
+
+packed_array!(i8, AParcel_writeByteArray, AParcel_readByteArray);
+packed_array!(f64, AParcel_writeDoubleArray, AParcel_readDoubleArray);
+packed_array!(f32, AParcel_writeFloatArray, AParcel_readFloatArray);
+packed_array!(i32, AParcel_writeInt32Array, AParcel_readInt32Array);
+packed_array!(i64, AParcel_writeInt64Array, AParcel_readInt64Array);
+packed_array!(u32, AParcel_writeUint32Array, AParcel_readUint32Array);
+packed_array!(u64, AParcel_writeUint64Array, AParcel_readUint64Array);
+
+pub unsafe extern "C" fn AParcel_writeCharArray(parcel: *mut AParcel, array: *const u16, length: i32) -> binder_status_t {
+    let parcel = &mut *parcel;
+    if length < 0 {
+        parcel.write_i32(-1);
+
+        return STATUS_OK;
+    }
+    if array.is_null() && length > 0 {
+        return STATUS_UNEXPECTED_NULL;
+    }
+
+    parcel.write_i32(length);
+    for idx in 0..length as usize {
+        parcel.write_i32(*array.add(idx) as i32);
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_readCharArray(
+    parcel: *const AParcel,
+    data: *mut c_void,
+    allocator: Option<unsafe extern "C" fn(*mut c_void, i32, *mut *mut u16) -> bool>,
+) -> binder_status_t {
+    let parcel = &*parcel;
+    let length = match parcel.read_i32() {
+        Ok(length) => length,
+        Err(err) => return err,
+    };
+    if length < -1 {
+        return STATUS_BAD_VALUE;
+    }
+
+    let mut array: *mut u16 = null_mut();
+    if !allocator.unwrap()(data, length, &mut array) {
+        return STATUS_NO_MEMORY;
+    }
+    if length <= 0 {
+        return STATUS_OK;
+    }
+    if array.is_null() {
+        return STATUS_NO_MEMORY;
+    }
+
+    for idx in 0..length as usize {
+        match parcel.read_i32() {
+            Ok(v) => *array.add(idx) = v as u16,
+            Err(err) => return err,
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_writeBoolArray(
+    parcel: *mut AParcel,
+    data: *const c_void,
+    length: i32,
+    getter: AParcel_boolArrayGetter,
+) -> binder_status_t {
+    let parcel = &mut *parcel;
+
+    parcel.write_i32(length.max(-1));
+    for idx in 0..length.max(0) as usize {
+        parcel.write_i32(getter.unwrap()(data, idx) as i32);
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_readBoolArray(
+    parcel: *const AParcel,
+    data: *mut c_void,
+    allocator: AParcel_boolArrayAllocator,
+    setter: AParcel_boolArraySetter,
+) -> binder_status_t {
+    let parcel = &*parcel;
+    let length = match parcel.read_i32() {
+        Ok(length) => length,
+        Err(err) => return err,
+    };
+    if length < -1 {
+        return STATUS_BAD_VALUE;
+    }
+    if !allocator.unwrap()(data, length) {
+        return STATUS_NO_MEMORY;
+    }
+
+    for idx in 0..length.max(0) as usize {
+        match parcel.read_i32() {
+            Ok(v) => setter.unwrap()(data, idx, v != 0),
+            Err(err) => return err,
+        }
+    }
+
+    STATUS_OK
+}
+
+unsafe fn write_utf8(parcel: &mut AParcel, string: *const c_char, length: i32) -> binder_status_t {
+    if string.is_null() {
+        if length != -1 {
+            return STATUS_UNEXPECTED_NULL;
+        }
+
+        parcel.write_string16(None);
+
+        return STATUS_OK;
+    }
+    if length < 0 {
+        return STATUS_BAD_VALUE;
+    }
+
+    match std::str::from_utf8(std::slice::from_raw_parts(string.cast::<u8>(), length as usize)) {
+        Ok(s) => {
+            parcel.write_string16(Some(s));
+
+            STATUS_OK
+        }
+        Err(_) => STATUS_BAD_VALUE,
+    }
+}
+
+unsafe fn read_utf8(parcel: &AParcel, allocate: impl FnOnce(i32, *mut *mut c_char) -> bool) -> binder_status_t {
+    let s = match parcel.read_string16() {
+        Ok(s) => s,
+        Err(err) => return err,
+    };
+
+    let mut buffer: *mut c_char = null_mut();
+    match s {
+        None => {
+            if !allocate(-1, &mut buffer) {
+                return STATUS_NO_MEMORY;
+            }
+        }
+        Some(s) => {
+            let s = match CString::new(s) {
+                Ok(s) => s,
+                Err(_) => return STATUS_BAD_VALUE,
+            };
+            let bytes = s.as_bytes_with_nul();
+
+            if !allocate(bytes.len() as i32, &mut buffer) || buffer.is_null() {
+                return STATUS_NO_MEMORY;
+            }
+
+            std::ptr::copy_nonoverlapping(bytes.as_ptr().cast(), buffer, bytes.len());
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_writeString(parcel: *mut AParcel, string: *const c_char, length: i32) -> binder_status_t {
+    write_utf8(&mut *parcel, string, length)
+}
+
+pub unsafe extern "C" fn AParcel_readString(
+    parcel: *const AParcel,
+    data: *mut c_void,
+    allocator: AParcel_stringAllocator,
+) -> binder_status_t {
+    read_utf8(&*parcel, |length, buffer| allocator.unwrap()(data, length, buffer))
+}
+
+pub unsafe extern "C" fn AParcel_writeStringArray(
+    parcel: *mut AParcel,
+    data: *const c_void,
+    length: i32,
+    getter: AParcel_stringArrayElementGetter,
+) -> binder_status_t {
+    let parcel = &mut *parcel;
+
+    parcel.write_i32(length.max(-1));
+    for idx in 0..length.max(0) as usize {
+        let mut len: i32 = 0;
+        let s = getter.unwrap()(data, idx, &mut len);
+
+        let status = write_utf8(parcel, s, len);
+        if status != STATUS_OK {
+            return status;
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_readStringArray(
+    parcel: *const AParcel,
+    data: *mut c_void,
+    allocator: AParcel_stringArrayAllocator,
+    element_allocator: AParcel_stringArrayElementAllocator,
+) -> binder_status_t {
+    let parcel = &*parcel;
+    let length = match parcel.read_i32() {
+        Ok(length) => length,
+        Err(err) => return err,
+    };
+    if length < -1 {
+        return STATUS_BAD_VALUE;
+    }
+    if !allocator.unwrap()(data, length) {
+        return STATUS_NO_MEMORY;
+    }
+
+    for idx in 0..length.max(0) as usize {
+        let status = read_utf8(parcel, |length, buffer| element_allocator.unwrap()(data, idx, length, buffer));
+        if status != STATUS_OK {
+            return status;
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_writeParcelableArray(
+    parcel: *mut AParcel,
+    data: *const c_void,
+    length: i32,
+    writer: AParcel_writeParcelableElement,
+) -> binder_status_t {
+    (*parcel).write_i32(length.max(-1));
+    for idx in 0..length.max(0) as usize {
+        let status = writer.unwrap()(parcel, data, idx);
+        if status != STATUS_OK {
+            return status;
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_readParcelableArray(
+    parcel: *const AParcel,
+    data: *mut c_void,
+    allocator: AParcel_parcelableArrayAllocator,
+    reader: AParcel_readParcelableElement,
+) -> binder_status_t {
+    let length = match (*parcel).read_i32() {
+        Ok(length) => length,
+        Err(err) => return err,
+    };
+    if length < -1 {
+        return STATUS_BAD_VALUE;
+    }
+    if !allocator.unwrap()(data, length) {
+        return STATUS_NO_MEMORY;
+    }
+
+    for idx in 0..length.max(0) as usize {
+        let status = reader.unwrap()(parcel, data, idx);
+        if status != STATUS_OK {
+            return status;
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_writeStrongBinder(parcel: *mut AParcel, binder: *mut AIBinder) -> binder_status_t {
+    if !binder.is_null() {
+        AIBinder_incStrong(binder);
+    }
+
+    (*parcel).write_object(Object::Binder(binder));
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_readStrongBinder(parcel: *const AParcel, binder: *mut *mut AIBinder) -> binder_status_t {
+    match (*parcel).read_object() {
+        Ok(Object::Binder(ptr)) => {
+            if !ptr.is_null() {
+                AIBinder_incStrong(*ptr);
+            }
+
+            *binder = *ptr;
+
+            STATUS_OK
+        }
+        Ok(Object::Fd(_)) => STATUS_BAD_TYPE,
+        Err(err) => err,
+    }
+}
+
+pub unsafe extern "C" fn AParcel_writeParcelFileDescriptor(parcel: *mut AParcel, fd: c_int) -> binder_status_t {
+    let parcel = &mut *parcel;
+    if fd < 0 {
+        parcel.write_i32(0);
+
+        return STATUS_OK;
+    }
+
+    match BorrowedFd::borrow_raw(fd).try_clone_to_owned() {
+        Ok(fd) => {
+            parcel.write_i32(1);
+            parcel.write_object(Object::Fd(fd));
+
+            STATUS_OK
+        }
+        Err(_) => STATUS_BAD_VALUE,
+    }
+}
+
+pub unsafe extern "C" fn AParcel_readParcelFileDescriptor(parcel: *const AParcel, fd: *mut c_int) -> binder_status_t {
+    let parcel = &*parcel;
+    match parcel.read_i32() {
+        Ok(0) => {
+            *fd = -1;
+
+            return STATUS_OK;
+        }
+        Ok(_) => {}
+        Err(err) => return err,
+    }
+
+    match parcel.read_object() {
+        Ok(Object::Fd(owned)) => match owned.try_clone() {
+            Ok(owned) => {
+                *fd = owned.into_raw_fd();
+
+                STATUS_OK
+            }
+            Err(_) => STATUS_NO_MEMORY,
+        },
+        Ok(Object::Binder(_)) => STATUS_BAD_TYPE,
+        Err(err) => err,
+    }
+}
+
+pub unsafe extern "C" fn AParcel_writeStatusHeader(parcel: *mut AParcel, status: *const AStatus) -> binder_status_t {
+    let (parcel, status) = (&mut *parcel, &*status);
+    if status.exception == EX_TRANSACTION_FAILED {
+        return status.code;
+    }
+
+    parcel.write_i32(status.exception);
+    if status.exception != EX_NONE {
+        parcel.write_string16(Some(&status.message.to_string_lossy()));
+        parcel.write_i32(0);
+
+        if status.exception == EX_SERVICE_SPECIFIC {
+            parcel.write_i32(status.code);
+        } else if status.exception == EX_PARCELABLE {
+            parcel.write_i32(0);
+        }
+    }
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn AParcel_readStatusHeader(parcel: *const AParcel, status: *mut *mut AStatus) -> binder_status_t {
+    let parcel = &*parcel;
+
+    let result = (|| {
+        let mut exception = parcel.read_i32()?;
+        if exception == EX_HAS_REPLY_HEADER {
+            let size = parcel.read_i32()?;
+            parcel.read_raw((size.max(4) - 4) as usize)?;
+            exception = EX_NONE;
+        }
+        if exception == EX_NONE {
+            return Ok(AStatus::new(EX_NONE, STATUS_OK, CString::default()));
+        }
+
+        let message = parcel.read_string16()?.unwrap_or_default();
+        let trace_size = parcel.read_i32()?;
+        if trace_size > 0 {
+            parcel.read_raw(trace_size as usize)?;
+        }
+
+        let code = match exception {
+            EX_SERVICE_SPECIFIC => parcel.read_i32()?,
+            EX_PARCELABLE => {
+                let size = parcel.read_i32()?;
+                parcel.read_raw((size.max(4) - 4) as usize)?;
+
+                STATUS_OK
+            }
+            _ => STATUS_OK,
+        };
+
+        Ok(AStatus::new(exception, code, CString::new(message).unwrap_or_default()))
+    })();
+
+    match result {
+        Ok(ptr) => {
+            *status = ptr;
+
+            STATUS_OK
+        }
+        Err(err) => err,
+    }
+}
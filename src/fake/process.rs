@@ -0,0 +1,41 @@
+use std::{
+    ffi::c_int,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use super::{binder_status_t, STATUS_OK, STATUS_UNKNOWN_ERROR};
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+static POLLING_FD: OnceLock<c_int> = OnceLock::new();
+
+pub unsafe extern "C" fn ABinderProcess_startThreadPool() {
+    STARTED.store(true, Ordering::SeqCst);
+}
+
+pub unsafe extern "C" fn ABinderProcess_setThreadPoolMaxThreadCount(_count: u32) -> bool {
+    !STARTED.load(Ordering::SeqCst)
+}
+
+pub unsafe extern "C" fn ABinderProcess_joinThreadPool() {
+    loop {
+        std::thread::park();
+    }
+}
+
+pub unsafe extern "C" fn ABinderProcess_setupPolling(fd: *mut c_int) -> binder_status_t {
+    let polling = *POLLING_FD.get_or_init(|| libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK));
+    if polling < 0 {
+        return STATUS_UNKNOWN_ERROR;
+    }
+
+    *fd = polling;
+
+    STATUS_OK
+}
+
+pub unsafe extern "C" fn ABinderProcess_handlePolledCommands() -> binder_status_t {
+    STATUS_OK
+}
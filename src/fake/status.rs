@@ -0,0 +1,132 @@
+use std::ffi::{c_char, CStr, CString};
+
+use super::{
+    binder_exception_t, binder_status_t, EX_NONE, EX_PARCELABLE, EX_SECURITY, EX_SERVICE_SPECIFIC, EX_TRANSACTION_FAILED,
+    STATUS_FAILED_TRANSACTION, STATUS_OK,
+};
+
+pub struct AStatus {
+    pub(super) exception: binder_exception_t,
+    pub(super) code: i32,
+    pub(super) message: CString,
+}
+
+impl AStatus {
+    pub(super) fn new(exception: binder_exception_t, code: i32, message: CString) -> *mut AStatus {
+        Box::into_raw(Box::new(AStatus {
+            exception,
+            code,
+            message,
+        }))
+    }
+}
+
+fn message_from_raw(message: *const c_char) -> CString {
+    if message.is_null() {
+        CString::default()
+    } else {
+        unsafe { CStr::from_ptr(message).to_owned() }
+    }
+}
+
+pub unsafe extern "C" fn AStatus_newOk() -> *mut AStatus {
+    AStatus::new(EX_NONE, STATUS_OK, CString::default())
+}
+
+pub unsafe extern "C" fn AStatus_fromExceptionCode(exception: binder_exception_t) -> *mut AStatus {
+    AStatus_fromExceptionCodeWithMessage(exception, std::ptr::null())
+}
+
+pub unsafe extern "C" fn AStatus_fromExceptionCodeWithMessage(
+    exception: binder_exception_t,
+    message: *const c_char,
+) -> *mut AStatus {
+    let code = if exception == EX_TRANSACTION_FAILED {
+        STATUS_FAILED_TRANSACTION
+    } else {
+        STATUS_OK
+    };
+
+    AStatus::new(exception, code, message_from_raw(message))
+}
+
+pub unsafe extern "C" fn AStatus_fromServiceSpecificError(error: i32) -> *mut AStatus {
+    AStatus_fromServiceSpecificErrorWithMessage(error, std::ptr::null())
+}
+
+pub unsafe extern "C" fn AStatus_fromServiceSpecificErrorWithMessage(error: i32, message: *const c_char) -> *mut AStatus {
+    AStatus::new(EX_SERVICE_SPECIFIC, error, message_from_raw(message))
+}
+
+pub unsafe extern "C" fn AStatus_fromStatus(status: binder_status_t) -> *mut AStatus {
+    if status == STATUS_OK {
+        AStatus_newOk()
+    } else {
+        AStatus::new(EX_TRANSACTION_FAILED, status, CString::default())
+    }
+}
+
+pub unsafe extern "C" fn AStatus_isOk(status: *const AStatus) -> bool {
+    (*status).exception == EX_NONE
+}
+
+pub unsafe extern "C" fn AStatus_getExceptionCode(status: *const AStatus) -> binder_exception_t {
+    (*status).exception
+}
+
+pub unsafe extern "C" fn AStatus_getServiceSpecificError(status: *const AStatus) -> i32 {
+    if (*status).exception == EX_SERVICE_SPECIFIC {
+        (*status).code
+    } else {
+        0
+    }
+}
+
+pub unsafe extern "C" fn AStatus_getStatus(status: *const AStatus) -> binder_status_t {
+    if (*status).exception == EX_TRANSACTION_FAILED {
+        (*status).code
+    } else {
+        STATUS_OK
+    }
+}
+
+pub unsafe extern "C" fn AStatus_getMessage(status: *const AStatus) -> *const c_char {
+    (*status).message.as_ptr()
+}
+
+pub unsafe extern "C" fn AStatus_getDescription(status: *const AStatus) -> *const c_char {
+    let status = &*status;
+
+    let description = match status.exception {
+        EX_NONE => "No error".to_string(),
+        EX_TRANSACTION_FAILED => format!("Status({}, EX_TRANSACTION_FAILED): {}", status.exception, status.code),
+        EX_SERVICE_SPECIFIC => format!(
+            "Status({}, EX_SERVICE_SPECIFIC): '{}: {}'",
+            status.exception,
+            status.code,
+            status.message.to_string_lossy()
+        ),
+        ex => format!(
+            "Status({}, {}): '{}'",
+            ex,
+            match ex {
+                EX_SECURITY => "EX_SECURITY",
+                EX_PARCELABLE => "EX_PARCELABLE",
+                _ => "EX_UNKNOWN",
+            },
+            status.message.to_string_lossy()
+        ),
+    };
+
+    CString::new(description).unwrap_or_default().into_raw()
+}
+
+pub unsafe extern "C" fn AStatus_deleteDescription(description: *const c_char) {
+    drop(CString::from_raw(description.cast_mut()))
+}
+
+pub unsafe extern "C" fn AStatus_delete(status: *mut AStatus) {
+    if !status.is_null() {
+        drop(Box::from_raw(status))
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Formatter},
+    os::fd::RawFd,
+};
+
+use crate::{CallingContext, Class, Code, Exception, Parcel, Status};
+
+pub enum Policy {
+    Allow,
+    Deny,
+    Uids(HashSet<u32>),
+    Pid(u32),
+    #[cfg(feature = "calling_sid")]
+    Sid(String),
+    SameUid,
+    All(Vec<Policy>),
+    Any(Vec<Policy>),
+}
+
+impl Policy {
+    pub fn uids(uids: impl IntoIterator<Item = u32>) -> Policy {
+        Policy::Uids(uids.into_iter().collect())
+    }
+
+    #[cfg(feature = "calling_sid")]
+    pub fn sid(pattern: impl Into<String>) -> Policy {
+        Policy::Sid(pattern.into())
+    }
+
+    pub fn check(&self, context: &CallingContext) -> Result<(), String> {
+        match self {
+            Policy::Allow => Ok(()),
+            Policy::Deny => Err("all callers are denied".to_string()),
+            Policy::Uids(uids) => {
+                if uids.contains(&context.uid) {
+                    Ok(())
+                } else {
+                    Err(format!("uid {} is not allowed", context.uid))
+                }
+            }
+            Policy::Pid(pid) => {
+                if *pid == context.pid {
+                    Ok(())
+                } else {
+                    Err(format!("pid {} is not allowed", context.pid))
+                }
+            }
+            #[cfg(feature = "calling_sid")]
+            Policy::Sid(pattern) => match &context.sid {
+                Some(sid) if matches_pattern(pattern.as_bytes(), sid.as_bytes()) => Ok(()),
+                Some(sid) => Err(format!("sid {} does not match {}", sid, pattern)),
+                None => Err(format!("sid is unavailable, required to match {}", pattern)),
+            },
+            Policy::SameUid => {
+                let uid = unsafe { libc::getuid() };
+                if uid == context.uid {
+                    Ok(())
+                } else {
+                    Err(format!("uid {} is not the service uid {}", context.uid, uid))
+                }
+            }
+            Policy::All(policies) => policies.iter().try_for_each(|policy| policy.check(context)),
+            Policy::Any(policies) => {
+                let mut reasons = Vec::with_capacity(policies.len());
+
+                for policy in policies {
+                    match policy.check(context) {
+                        Ok(_) => return Ok(()),
+                        Err(reason) => reasons.push(reason),
+                    }
+                }
+
+                if reasons.is_empty() {
+                    Err("no policy allows the caller".to_string())
+                } else {
+                    Err(reasons.join(", "))
+                }
+            }
+        }
+    }
+}
+
+impl Debug for Policy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Policy::Allow => f.write_str("Allow"),
+            Policy::Deny => f.write_str("Deny"),
+            Policy::Uids(uids) => f.write_fmt(format_args!("Uids({:?})", uids)),
+            Policy::Pid(pid) => f.write_fmt(format_args!("Pid({})", pid)),
+            #[cfg(feature = "calling_sid")]
+            Policy::Sid(pattern) => f.write_fmt(format_args!("Sid({:?})", pattern)),
+            Policy::SameUid => f.write_str("SameUid"),
+            Policy::All(policies) => f.write_fmt(format_args!("All({:?})", policies)),
+            Policy::Any(policies) => f.write_fmt(format_args!("Any({:?})", policies)),
+        }
+    }
+}
+
+#[cfg(feature = "calling_sid")]
+fn matches_pattern(pattern: &[u8], value: &[u8]) -> bool {
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    v = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+pub struct Guarded<T: Class> {
+    service: T,
+    default: Policy,
+    policies: HashMap<u32, Policy>,
+}
+
+impl<T: Class> Guarded<T> {
+    pub fn new(service: T) -> Self {
+        Guarded {
+            service,
+            default: Policy::Allow,
+            policies: HashMap::new(),
+        }
+    }
+
+    pub fn with_default(mut self, policy: Policy) -> Self {
+        self.default = policy;
+        self
+    }
+
+    pub fn with_policy(mut self, code: u32, policy: Policy) -> Self {
+        self.policies.insert(code, policy);
+        self
+    }
+
+    pub fn service(&self) -> &T {
+        &self.service
+    }
+
+    pub fn policy(&self, code: u32) -> &Policy {
+        self.policies.get(&code).unwrap_or(&self.default)
+    }
+
+    pub fn check(&self, code: u32, context: &CallingContext) -> Result<(), Status> {
+        match self.policy(code).check(context) {
+            Ok(_) => {
+                #[cfg(feature = "calling_sid")]
+                log::debug!(
+                    "{}: allowed transaction {} from uid={} pid={} sid={:?}",
+                    T::INTERFACE_NAME,
+                    code,
+                    context.uid,
+                    context.pid,
+                    context.sid
+                );
+                #[cfg(not(feature = "calling_sid"))]
+                log::debug!(
+                    "{}: allowed transaction {} from uid={} pid={}",
+                    T::INTERFACE_NAME,
+                    code,
+                    context.uid,
+                    context.pid
+                );
+
+                Ok(())
+            }
+            Err(reason) => {
+                let msg = format!("{}: transaction {} denied: {}", T::INTERFACE_NAME, code, reason);
+
+                log::warn!("{} (pid={})", msg, context.pid);
+
+                Err(Status::with_exception_and_message(Exception::Security, &msg)
+                    .unwrap_or_else(|_| Status::with_exception(Exception::Security)))
+            }
+        }
+    }
+}
+
+impl<T: Class> Class for Guarded<T> {
+    const INTERFACE_NAME: &'static str = T::INTERFACE_NAME;
+
+    #[cfg(feature = "api-33")]
+    fn disable_interface_token_header() -> bool {
+        T::disable_interface_token_header()
+    }
+
+    fn enable_status_header() -> bool {
+        T::enable_status_header()
+    }
+
+    #[cfg(feature = "stability")]
+    fn stability() -> Option<crate::Stability> {
+        T::stability()
+    }
+
+    /// Always requested, otherwise `Policy::Sid` can never match.
+    #[cfg(feature = "calling_sid")]
+    fn requesting_sid() -> bool {
+        true
+    }
+
+    /// Without the status header the `Security` exception can not reach the client, the transaction fails with
+    /// `PermissionDenied` instead.
+    fn on_transact(&self, code: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+        if let Err(err) = self.check(code, &CallingContext::current()) {
+            return Err(if T::enable_status_header() {
+                err
+            } else {
+                Status::with_code(Code::PermissionDenied)
+            });
+        }
+
+        self.service.on_transact(code, data, reply)
+    }
+
+    fn on_dump(&self, fd: RawFd, args: &[&str]) -> Result<(), Status> {
+        self.service.on_dump(fd, args)
+    }
+}
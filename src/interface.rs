@@ -0,0 +1,126 @@
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+};
+
+use crate::{
+    sys::{AIBinder_Class, AIBinder_associateClass},
+    Code, IBinder, Parcel, Read, ReadArray, Status, Write, WriteArray,
+};
+
+pub trait Interface {
+    const INTERFACE_NAME: &'static str;
+
+    #[doc(hidden)]
+    fn _class() -> usize;
+}
+
+pub struct Strong<I: ?Sized + Interface> {
+    binder: IBinder,
+    _interface: PhantomData<fn() -> Box<I>>,
+}
+
+impl<I: ?Sized + Interface> Strong<I> {
+    pub fn new(binder: IBinder) -> Result<Self, Status> {
+        let associated = unsafe { AIBinder_associateClass(binder.as_raw(), I::_class() as *const AIBinder_Class) };
+
+        #[cfg(feature = "api-31")]
+        let associated =
+            associated || (!binder.is_remote() && binder.get_interface_descriptor().as_deref() == Some(I::INTERFACE_NAME));
+
+        if associated {
+            Ok(Strong {
+                binder,
+                _interface: PhantomData,
+            })
+        } else {
+            Err(Status::with_code(Code::BadType))
+        }
+    }
+
+    pub fn as_binder(&self) -> &IBinder {
+        &self.binder
+    }
+
+    pub fn into_binder(self) -> IBinder {
+        self.binder
+    }
+}
+
+impl<I: ?Sized + Interface> Clone for Strong<I> {
+    fn clone(&self) -> Self {
+        Strong {
+            binder: self.binder.clone(),
+            _interface: PhantomData,
+        }
+    }
+}
+
+impl<I: ?Sized + Interface> Debug for Strong<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}({:?})", I::INTERFACE_NAME, self.binder))
+    }
+}
+
+impl<I: ?Sized + Interface> From<Strong<I>> for IBinder {
+    fn from(value: Strong<I>) -> Self {
+        value.binder
+    }
+}
+
+impl<I: ?Sized + Interface> Read for Strong<I> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        Strong::new(parcel.read::<IBinder>()?)
+    }
+}
+
+impl<I: ?Sized + Interface> Write for &Strong<I> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write(self.as_binder())
+    }
+}
+
+impl<I: ?Sized + Interface> Write for Strong<I> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write(self.as_binder())
+    }
+}
+
+impl<I: ?Sized + Interface> Read for Option<Strong<I>> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        parcel.read::<Option<IBinder>>()?.map(Strong::new).transpose()
+    }
+}
+
+impl<I: ?Sized + Interface> Write for Option<&Strong<I>> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write(&self.map(Strong::as_binder))
+    }
+}
+
+impl<I: ?Sized + Interface> Write for Option<Strong<I>> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        <Option<&Strong<I>> as Write>::write(&self.as_ref(), parcel)
+    }
+}
+
+#[macro_export]
+macro_rules! define_interface {
+    ($interface:ty, $class:ty) => {
+        impl $crate::Interface for $interface {
+            const INTERFACE_NAME: &'static str = <$class as $crate::Class>::INTERFACE_NAME;
+
+            fn _class() -> usize {
+                <$class as $crate::DefinedClass>::_class()
+            }
+        }
+    };
+}
+
+impl<I: ?Sized + Interface> ReadArray for Option<Strong<I>> {}
+
+impl<I: ?Sized + Interface> WriteArray for Option<Strong<I>> {}
+
+impl<I: ?Sized + Interface> ReadArray for Strong<I> {}
+
+impl<I: ?Sized + Interface> WriteArray for Strong<I> {}
@@ -1,10 +1,40 @@
+#[cfg(feature = "async")]
+pub use asynchronous::*;
 pub use binder::*;
+pub use calling::*;
 pub use class::*;
+pub use client::*;
+pub use death::*;
+#[cfg(feature = "guard")]
+pub use guard::*;
+pub use interface::*;
+pub use middleware::*;
+#[cfg(feature = "derive")]
+pub use ndkbinder_derive::{Read, Write};
 pub use parcel::*;
+pub use parcelable_holder::*;
+#[cfg(feature = "process")]
+pub use process::*;
 #[cfg(feature = "service_manager")]
 pub use service_manager::*;
+#[cfg(feature = "shared_memory")]
+pub use shared_memory::*;
+#[cfg(feature = "stability")]
+pub use stability::*;
 pub use status::*;
+#[cfg(any(
+    feature = "calling_sid",
+    feature = "process",
+    feature = "sensitive",
+    feature = "service_manager",
+    feature = "stability",
+    all(feature = "shared_memory", target_os = "android")
+))]
+pub use symbol::*;
+#[cfg(feature = "api-31")]
+pub use transaction::*;
 
+#[cfg(not(feature = "host-fake"))]
 mod sys {
     #![allow(non_upper_case_globals)]
     #![allow(non_camel_case_types)]
@@ -13,11 +43,48 @@ mod sys {
     include!(concat!(env!("OUT_DIR"), "/binder_sys.rs"));
 }
 
+#[cfg(feature = "host-fake")]
+#[path = "fake/mod.rs"]
+mod sys;
+
+#[cfg(feature = "async")]
+mod asynchronous;
 mod binder;
+mod calling;
 mod class;
+mod client;
+mod death;
+#[cfg(feature = "guard")]
+mod guard;
+mod interface;
+mod middleware;
 mod parcel;
+mod parcelable_holder;
+mod pool;
+#[cfg(feature = "process")]
+mod process;
+#[cfg(feature = "sensitive")]
+mod sensitive;
 #[cfg(feature = "service_manager")]
 mod service_manager;
+#[cfg(feature = "shared_memory")]
+mod shared_memory;
+#[cfg(feature = "stability")]
+mod stability;
 mod status;
+#[cfg(any(
+    feature = "calling_sid",
+    feature = "process",
+    feature = "sensitive",
+    feature = "service_manager",
+    feature = "stability",
+    all(feature = "shared_memory", target_os = "android")
+))]
+mod symbol;
 #[cfg(test)]
 mod test;
+#[cfg(feature = "api-31")]
+mod transaction;
+
+#[cfg(test)]
+extern crate self as ndkbinder;
@@ -0,0 +1,122 @@
+use std::{
+    os::fd::RawFd,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{sys::AParcel_setDataPosition, CallingContext, Class, Parcel, Status};
+
+pub trait Middleware: Send + Sync {
+    fn before(&self, code: u32, data: &Parcel, context: &CallingContext) -> Result<(), Status> {
+        let _ = code;
+        let _ = data;
+        let _ = context;
+
+        Ok(())
+    }
+
+    fn after(
+        &self,
+        code: u32,
+        data: &Parcel,
+        reply: Option<&mut Parcel>,
+        context: &CallingContext,
+        elapsed: Duration,
+        result: Result<(), Status>,
+    ) -> Result<(), Status> {
+        let _ = code;
+        let _ = data;
+        let _ = reply;
+        let _ = context;
+        let _ = elapsed;
+
+        result
+    }
+}
+
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    fn before(&self, code: u32, data: &Parcel, context: &CallingContext) -> Result<(), Status> {
+        (**self).before(code, data, context)
+    }
+
+    fn after(
+        &self,
+        code: u32,
+        data: &Parcel,
+        reply: Option<&mut Parcel>,
+        context: &CallingContext,
+        elapsed: Duration,
+        result: Result<(), Status>,
+    ) -> Result<(), Status> {
+        (**self).after(code, data, reply, context, elapsed, result)
+    }
+}
+
+pub struct Layered<T: Class, M: Middleware> {
+    service: T,
+    middleware: M,
+}
+
+impl<T: Class, M: Middleware> Layered<T, M> {
+    pub fn new(service: T, middleware: M) -> Self {
+        Layered { service, middleware }
+    }
+
+    pub fn layer<N: Middleware>(self, middleware: N) -> Layered<Self, N> {
+        Layered::new(self, middleware)
+    }
+
+    pub fn service(&self) -> &T {
+        &self.service
+    }
+
+    pub fn middleware(&self) -> &M {
+        &self.middleware
+    }
+}
+
+impl<T: Class, M: Middleware> Class for Layered<T, M> {
+    const INTERFACE_NAME: &'static str = T::INTERFACE_NAME;
+
+    #[cfg(feature = "api-33")]
+    fn disable_interface_token_header() -> bool {
+        T::disable_interface_token_header()
+    }
+
+    fn enable_status_header() -> bool {
+        T::enable_status_header()
+    }
+
+    #[cfg(feature = "stability")]
+    fn stability() -> Option<crate::Stability> {
+        T::stability()
+    }
+
+    #[cfg(feature = "calling_sid")]
+    fn requesting_sid() -> bool {
+        T::requesting_sid()
+    }
+
+    fn on_transact(&self, code: u32, data: &Parcel, mut reply: Option<&mut Parcel>) -> Result<(), Status> {
+        let context = CallingContext::current();
+        let begin = Instant::now();
+
+        let position = data.get_data_position();
+        let rewind =
+            || unsafe { Status::from_raw_status_code(AParcel_setDataPosition(data.as_raw(), position as i32)).err(|| ()) };
+
+        let result = self.middleware.before(code, data, &context).and_then(|_| {
+            rewind()?;
+
+            self.service.on_transact(code, data, reply.as_deref_mut())
+        });
+
+        let result = result.and(rewind());
+
+        self.middleware.after(code, data, reply, &context, begin.elapsed(), result)
+    }
+
+    fn on_dump(&self, fd: RawFd, args: &[&str]) -> Result<(), Status> {
+        self.service.on_dump(fd, args)
+    }
+}
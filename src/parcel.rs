@@ -1,4 +1,5 @@
 use std::{
+    cell::Cell,
     ffi::{c_char, c_int, c_void},
     mem::MaybeUninit,
     os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd},
@@ -32,6 +33,31 @@ pub trait Write {
     fn write(&self, parcel: &mut Parcel) -> Result<(), Status>;
 }
 
+pub trait ReadArray: Read {
+    fn read_array(parcel: &Parcel) -> Result<Option<Vec<Self>>, Status> {
+        parcel.read_array::<Self>()
+    }
+}
+
+pub trait WriteArray: Write + Sized {
+    fn write_array(parcel: &mut Parcel, values: Option<&[Self]>) -> Result<(), Status> {
+        parcel.write_array(values)
+    }
+}
+
+pub trait ReadNullable: Read {
+    fn read_nullable(parcel: &Parcel) -> Result<Option<Self>, Status>;
+}
+
+pub trait WriteNullable: Write + Sized {
+    fn write_nullable(parcel: &mut Parcel, value: Option<&Self>) -> Result<(), Status> {
+        match value {
+            None => parcel.write(&0i32),
+            Some(value) => parcel.write(value),
+        }
+    }
+}
+
 enum RawParcel {
     Owned(*mut AParcel),
     Borrowed(*mut AParcel),
@@ -63,9 +89,18 @@ impl Parcel {
         }
     }
 
+    pub(crate) fn into_raw(self) -> *mut AParcel {
+        let ptr = self.as_raw();
+
+        std::mem::forget(self);
+
+        ptr
+    }
+
     #[cfg(all(feature = "api-30", feature = "jni"))]
     pub unsafe fn from_java(env: *mut jni_sys::JNIEnv, obj: jni_sys::jobject) -> Option<Parcel> {
         unsafe {
+            #[cfg_attr(feature = "host-fake", allow(improper_ctypes))]
             extern "C" {
                 fn AParcel_fromJavaParcel(env: *mut jni_sys::JNIEnv, obj: jni_sys::jobject) -> *mut AParcel;
             }
@@ -148,14 +183,14 @@ impl Parcel {
         unsafe {
             let mut ret: Option<Vec<T>> = None;
 
-            unsafe extern "C" fn reader<T: Read>(parcel: *const AParcel, data: *mut c_void, index: usize) -> binder_status_t {
+            unsafe extern "C" fn reader<T: Read>(parcel: *const AParcel, data: *mut c_void, _index: usize) -> binder_status_t {
                 let data = &mut *data.cast::<Option<Vec<T>>>();
 
                 let parcel = Parcel::from_borrow_raw(parcel.cast_mut());
 
                 match parcel.read::<T>() {
                     Ok(v) => {
-                        std::mem::forget(std::mem::replace(&mut data.as_mut().unwrap()[index], v));
+                        data.as_mut().unwrap().push(v);
 
                         Code::Ok.as_raw()
                     }
@@ -198,6 +233,155 @@ impl Parcel {
             .err(|| ())
         }
     }
+
+    pub fn write_bytes(&mut self, data: &[u8]) -> Result<(), Status> {
+        unsafe {
+            Status::from_raw_status_code(AParcel_writeByteArray(self.as_raw(), data.as_ptr().cast(), data.len() as i32))
+                .err(|| ())
+        }
+    }
+
+    pub fn read_bytes_into(&self, buffer: &mut Vec<u8>) -> Result<(), Status> {
+        if self.read_nullable_bytes_into(buffer)? {
+            Ok(())
+        } else {
+            Err(Status::with_code(Code::UnexpectedNull))
+        }
+    }
+
+    pub fn read_byte_slice_with<R, F>(&self, body: F) -> Result<R, Status>
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        thread_local! {
+            static BUFFER: Cell<Vec<u8>> = const { Cell::new(Vec::new()) };
+        }
+
+        let mut buffer = BUFFER.take();
+        let ret = self.read_bytes_into(&mut buffer).map(|_| body(&buffer));
+        buffer.clear();
+        BUFFER.set(buffer);
+
+        ret
+    }
+
+    fn read_nullable_bytes_into(&self, buffer: &mut Vec<u8>) -> Result<bool, Status> {
+        struct Target<'a> {
+            buffer: &'a mut Vec<u8>,
+            length: i32,
+        }
+
+        unsafe {
+            unsafe extern "C" fn allocator(data: *mut c_void, length: i32, out_buffer: *mut *mut i8) -> bool {
+                let target = &mut *data.cast::<Target>();
+
+                target.length = length;
+                if length >= 0 {
+                    target.buffer.reserve(length as usize);
+                    *out_buffer = target.buffer.as_mut_ptr().cast();
+                }
+
+                true
+            }
+
+            buffer.clear();
+
+            let mut target = Target { buffer, length: -1 };
+
+            Status::from_raw_status_code(AParcel_readByteArray(
+                self.as_raw(),
+                (&mut target as *mut Target).cast(),
+                Some(allocator),
+            ))
+            .err(|| ())?;
+
+            if target.length < 0 {
+                Ok(false)
+            } else {
+                target.buffer.set_len(target.length as usize);
+
+                Ok(true)
+            }
+        }
+    }
+
+    pub fn write_sized<F>(&mut self, body: F) -> Result<(), Status>
+    where
+        F: FnOnce(&mut Parcel) -> Result<(), Status>,
+    {
+        let start = self.get_data_position();
+
+        self.write(&0i32)?;
+
+        body(self)?;
+
+        let end = self.get_data_position();
+
+        self.set_data_position(start)?;
+        self.write(&((end - start) as i32))?;
+        self.set_data_position(end)
+    }
+
+    pub fn read_out_array_len(&self) -> Result<Option<usize>, Status> {
+        let len: i32 = self.read()?;
+        if len < 0 {
+            return Ok(None);
+        }
+
+        #[cfg(feature = "api-31")]
+        if len as u32 > self.get_data_size() {
+            return Err(Status::with_code(Code::NoMemory));
+        }
+
+        Ok(Some(len as usize))
+    }
+
+    pub fn read_sized<T, F>(&self, body: F) -> Result<T, Status>
+    where
+        F: FnOnce(&SizedReader) -> Result<T, Status>,
+    {
+        let start = self.get_data_position();
+        let size: i32 = self.read()?;
+        if size < 4 {
+            return Err(Status::bad_value());
+        }
+
+        let end = start
+            .checked_add(size as u32)
+            .filter(|end| *end <= i32::MAX as u32)
+            .ok_or_else(Status::bad_value)?;
+
+        let value = body(&SizedReader { parcel: self, end })?;
+
+        unsafe { Status::from_raw_status_code(AParcel_setDataPosition(self.as_raw(), end as i32)).err(|| value) }
+    }
+}
+
+pub struct SizedReader<'a> {
+    parcel: &'a Parcel,
+    end: u32,
+}
+
+impl<'a> SizedReader<'a> {
+    pub fn has_more_data(&self) -> bool {
+        self.parcel.get_data_position() < self.end
+    }
+
+    pub fn read<T: Read>(&self) -> Result<T, Status> {
+        if self.has_more_data() {
+            self.parcel.read()
+        } else {
+            Err(Status::bad_value())
+        }
+    }
+
+    pub fn read_or_default<T: Read + Default>(&self) -> Result<T, Status> {
+        if self.has_more_data() {
+            self.parcel.read()
+        } else {
+            Ok(T::default())
+        }
+    }
 }
 
 fn read_basic_type<T: Sized>(
@@ -291,21 +475,15 @@ impls_for_basic_type!(u64, AParcel_writeUint64, AParcel_readUint64);
 
 macro_rules! impls_for_basic_type_array {
     ($typ:ty, $write_array:ident, $read_array:ident) => {
-        impl Read for Option<Vec<$typ>> {
-            fn read(parcel: &Parcel) -> Result<Self, Status> {
+        impl ReadArray for $typ {
+            fn read_array(parcel: &Parcel) -> Result<Option<Vec<Self>>, Status> {
                 read_basic_type_array(parcel.as_raw(), $read_array)
             }
         }
 
-        impl Write for Option<Vec<$typ>> {
-            fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
-                write_basic_type_array(parcel.as_raw(), self.as_deref(), $write_array)
-            }
-        }
-
-        impl Write for Option<&[$typ]> {
-            fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
-                write_basic_type_array(parcel.as_raw(), self.as_deref(), $write_array)
+        impl WriteArray for $typ {
+            fn write_array(parcel: &mut Parcel, values: Option<&[Self]>) -> Result<(), Status> {
+                write_basic_type_array(parcel.as_raw(), values, $write_array)
             }
         }
     };
@@ -320,6 +498,35 @@ impls_for_basic_type_array!(i64, AParcel_writeInt64Array, AParcel_readInt64Array
 impls_for_basic_type_array!(u32, AParcel_writeUint32Array, AParcel_readUint32Array);
 impls_for_basic_type_array!(u64, AParcel_writeUint64Array, AParcel_readUint64Array);
 
+impl Read for u8 {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        Ok(parcel.read::<i8>()? as u8)
+    }
+}
+
+impl Write for u8 {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write(&(*self as i8))
+    }
+}
+
+impl ReadArray for u8 {
+    fn read_array(parcel: &Parcel) -> Result<Option<Vec<Self>>, Status> {
+        let mut buffer = Vec::new();
+
+        Ok(parcel.read_nullable_bytes_into(&mut buffer)?.then_some(buffer))
+    }
+}
+
+impl WriteArray for u8 {
+    fn write_array(parcel: &mut Parcel, values: Option<&[Self]>) -> Result<(), Status> {
+        match values {
+            None => write_basic_type_array::<i8>(parcel.as_raw(), None, AParcel_writeByteArray),
+            Some(values) => parcel.write_bytes(values),
+        }
+    }
+}
+
 unsafe extern "C" fn typed_nullable_array_allocator<T>(data: *mut c_void, length: i32) -> bool {
     let data = &mut *data.cast::<Option<Vec<T>>>();
 
@@ -327,21 +534,20 @@ unsafe extern "C" fn typed_nullable_array_allocator<T>(data: *mut c_void, length
         *data = None;
     } else {
         *data = Some(Vec::with_capacity(length as usize));
-        data.as_mut().unwrap().set_len(length as usize);
     }
 
     true
 }
 
-impl Read for Option<Vec<bool>> {
-    fn read(parcel: &Parcel) -> Result<Self, Status> {
+impl ReadArray for bool {
+    fn read_array(parcel: &Parcel) -> Result<Option<Vec<Self>>, Status> {
         let mut data: Option<Vec<bool>> = None;
 
         unsafe {
-            unsafe extern "C" fn setter(data: *mut c_void, index: usize, value: bool) {
+            unsafe extern "C" fn setter(data: *mut c_void, _index: usize, value: bool) {
                 let data = &mut *data.cast::<Option<Vec<bool>>>();
 
-                data.as_mut().unwrap()[index] = value;
+                data.as_mut().unwrap().push(value);
             }
 
             Status::from_raw_status_code(AParcel_readBoolArray(
@@ -355,8 +561,8 @@ impl Read for Option<Vec<bool>> {
     }
 }
 
-impl Write for Option<&[bool]> {
-    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+impl WriteArray for bool {
+    fn write_array(parcel: &mut Parcel, values: Option<&[Self]>) -> Result<(), Status> {
         unsafe {
             unsafe extern "C" fn getter(data: *const c_void, index: usize) -> bool {
                 let data = &*data.cast::<Option<&[bool]>>();
@@ -366,8 +572,8 @@ impl Write for Option<&[bool]> {
 
             Status::from_raw_status_code(AParcel_writeBoolArray(
                 parcel.as_raw(),
-                (self as *const Self).cast(),
-                match self {
+                (&values as *const Option<&[bool]>).cast(),
+                match values {
                     None => -1,
                     Some(a) => a.len() as i32,
                 },
@@ -378,12 +584,6 @@ impl Write for Option<&[bool]> {
     }
 }
 
-impl Write for Option<Vec<bool>> {
-    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
-        <Option<&[bool]> as Write>::write(&self.as_deref(), parcel)
-    }
-}
-
 impl Read for Status {
     fn read(parcel: &Parcel) -> Result<Self, Status> {
         unsafe {
@@ -486,26 +686,26 @@ impl Write for Option<String> {
     }
 }
 
-impl Read for Option<Vec<Option<String>>> {
-    fn read(parcel: &Parcel) -> Result<Self, Status> {
+impl ReadArray for Option<String> {
+    fn read_array(parcel: &Parcel) -> Result<Option<Vec<Self>>, Status> {
         unsafe {
             let mut data: Option<Vec<Option<Vec<u8>>>> = None;
 
             unsafe extern "C" fn element_allocator(
                 data: *mut c_void,
-                index: usize,
+                _index: usize,
                 length: i32,
                 buffer: *mut *mut c_char,
             ) -> bool {
                 let data = &mut *data.cast::<Option<Vec<Option<Vec<u8>>>>>();
 
                 if length < 0 {
-                    data.as_mut().unwrap()[index] = None;
+                    data.as_mut().unwrap().push(None);
                 } else {
                     let mut array: Vec<u8> = Vec::with_capacity(length as usize);
                     array.set_len((length - 1) as usize);
                     *buffer = array.as_mut_ptr().cast();
-                    std::mem::forget(std::mem::replace(&mut data.as_mut().unwrap()[index], Some(array)));
+                    data.as_mut().unwrap().push(Some(array));
                 }
 
                 true
@@ -540,11 +740,11 @@ impl Read for Option<Vec<Option<String>>> {
     }
 }
 
-impl Write for Option<Vec<Option<String>>> {
-    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+impl WriteArray for Option<String> {
+    fn write_array(parcel: &mut Parcel, values: Option<&[Self]>) -> Result<(), Status> {
         unsafe {
             unsafe extern "C" fn getter(data: *const c_void, index: usize, length: *mut i32) -> *const c_char {
-                let data = &*data.cast::<Option<Vec<Option<String>>>>();
+                let data = &*data.cast::<Option<&[Option<String>]>>();
 
                 let s = data.as_ref().unwrap()[index].as_ref();
                 match s {
@@ -563,8 +763,8 @@ impl Write for Option<Vec<Option<String>>> {
 
             Status::from_raw_status_code(AParcel_writeStringArray(
                 parcel.as_raw(),
-                (self as *const Option<Vec<Option<String>>>).cast(),
-                match self {
+                (&values as *const Option<&[Option<String>]>).cast(),
+                match values {
                     None => -1,
                     Some(v) => v.len() as i32,
                 },
@@ -611,3 +811,254 @@ impl Write for Option<IBinder> {
         <Option<&IBinder> as Write>::write(&self.as_ref(), parcel)
     }
 }
+
+impl Read for OwnedFd {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        parcel
+            .read::<Option<OwnedFd>>()?
+            .ok_or_else(|| Status::with_code(Code::UnexpectedNull))
+    }
+}
+
+impl<'a> Write for BorrowedFd<'a> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        Some(*self).write(parcel)
+    }
+}
+
+impl Write for OwnedFd {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        Some(self.as_fd()).write(parcel)
+    }
+}
+
+impl Read for String {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        parcel
+            .read::<Option<String>>()?
+            .ok_or_else(|| Status::with_code(Code::UnexpectedNull))
+    }
+}
+
+impl Write for &str {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        Some(*self).write(parcel)
+    }
+}
+
+impl Write for String {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        Some(self.as_str()).write(parcel)
+    }
+}
+
+impl Read for IBinder {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        parcel
+            .read::<Option<IBinder>>()?
+            .ok_or_else(|| Status::with_code(Code::UnexpectedNull))
+    }
+}
+
+impl Write for &IBinder {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        Some(*self).write(parcel)
+    }
+}
+
+impl Write for IBinder {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        Some(self).write(parcel)
+    }
+}
+
+impl ReadArray for OwnedFd {}
+
+impl WriteArray for OwnedFd {}
+
+impl ReadArray for String {}
+
+impl WriteArray for String {}
+
+impl WriteArray for &str {}
+
+impl WriteArray for Option<&str> {}
+
+impl ReadArray for IBinder {}
+
+impl WriteArray for IBinder {}
+
+impl ReadArray for Option<OwnedFd> {}
+
+impl WriteArray for Option<OwnedFd> {}
+
+impl ReadArray for Option<IBinder> {}
+
+impl WriteArray for Option<IBinder> {}
+
+impl<T: ReadArray> Read for Option<Vec<T>> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        T::read_array(parcel)
+    }
+}
+
+impl<T: ReadArray> Read for Vec<T> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        T::read_array(parcel)?.ok_or_else(|| Status::with_code(Code::UnexpectedNull))
+    }
+}
+
+impl<T: WriteArray> Write for Option<&[T]> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        T::write_array(parcel, *self)
+    }
+}
+
+impl<T: WriteArray> Write for Option<Vec<T>> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        T::write_array(parcel, self.as_deref())
+    }
+}
+
+impl<T: WriteArray> Write for &[T] {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        T::write_array(parcel, Some(self))
+    }
+}
+
+impl<T: WriteArray> Write for Vec<T> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        T::write_array(parcel, Some(self))
+    }
+}
+
+impl<T: ReadArray> ReadArray for Option<Vec<T>> {}
+
+impl<T: WriteArray> WriteArray for Option<Vec<T>> {}
+
+impl<T: ReadArray> ReadArray for Vec<T> {}
+
+impl<T: WriteArray> WriteArray for Vec<T> {}
+
+impl<T: ReadNullable> Read for Option<T> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        T::read_nullable(parcel)
+    }
+}
+
+impl<T: WriteNullable> Write for Option<T> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        T::write_nullable(parcel, self.as_ref())
+    }
+}
+
+impl<T: ReadNullable> ReadArray for Option<T> {}
+
+impl<T: WriteNullable> WriteArray for Option<T> {}
+
+impl<T: ReadArray, const N: usize> Read for [T; N] {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        parcel.read::<Vec<T>>()?.try_into().map_err(|_| Status::bad_value())
+    }
+}
+
+impl<T: ReadArray, const N: usize> Read for Option<[T; N]> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        parcel
+            .read::<Option<Vec<T>>>()?
+            .map(|v| v.try_into().map_err(|_| Status::bad_value()))
+            .transpose()
+    }
+}
+
+impl<T: WriteArray, const N: usize> Write for [T; N] {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        T::write_array(parcel, Some(self))
+    }
+}
+
+impl<T: WriteArray, const N: usize> Write for Option<[T; N]> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        T::write_array(parcel, self.as_ref().map(|v| &v[..]))
+    }
+}
+
+impl<T: ReadArray, const N: usize> ReadArray for [T; N] {}
+
+impl<T: WriteArray, const N: usize> WriteArray for [T; N] {}
+
+impl<T: ReadArray, const N: usize> ReadArray for Option<[T; N]> {}
+
+impl<T: WriteArray, const N: usize> WriteArray for Option<[T; N]> {}
+
+macro_rules! impls_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Read),+> Read for ($($name,)+) {
+            fn read(parcel: &Parcel) -> Result<Self, Status> {
+                Ok(($(parcel.read::<$name>()?,)+))
+            }
+        }
+
+        impl<$($name: Write),+> Write for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+                let ($($name,)+) = self;
+                $(parcel.write($name)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl Read for () {
+    fn read(_: &Parcel) -> Result<Self, Status> {
+        Ok(())
+    }
+}
+
+impl Write for () {
+    fn write(&self, _: &mut Parcel) -> Result<(), Status> {
+        Ok(())
+    }
+}
+
+impls_for_tuple!(A);
+impls_for_tuple!(A, B);
+impls_for_tuple!(A, B, C);
+impls_for_tuple!(A, B, C, D);
+impls_for_tuple!(A, B, C, D, E);
+impls_for_tuple!(A, B, C, D, E, F);
+impls_for_tuple!(A, B, C, D, E, F, G);
+impls_for_tuple!(A, B, C, D, E, F, G, H);
+impls_for_tuple!(A, B, C, D, E, F, G, H, I);
+impls_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impls_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impls_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(feature = "bytes")]
+impl Read for bytes::Bytes {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        Ok(parcel.read::<Vec<u8>>()?.into())
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Read for Option<bytes::Bytes> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        Ok(parcel.read::<Option<Vec<u8>>>()?.map(Into::into))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Write for bytes::Bytes {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write_bytes(self)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Write for Option<bytes::Bytes> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        u8::write_array(parcel, self.as_deref())
+    }
+}
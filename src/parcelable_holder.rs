@@ -0,0 +1,228 @@
+#[cfg(feature = "api-31")]
+use std::{
+    any::Any,
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex},
+};
+
+#[cfg(feature = "api-31")]
+use crate::{sys::AParcel_setDataPosition, Code, Parcel, Read, Status, Write};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ParcelableStability {
+    #[default]
+    Local,
+    Vintf,
+}
+
+impl ParcelableStability {
+    pub fn as_raw(&self) -> i32 {
+        match self {
+            ParcelableStability::Local => 0,
+            ParcelableStability::Vintf => 0b111111,
+        }
+    }
+
+    pub fn from_raw(raw: i32) -> Option<Self> {
+        match raw {
+            0 => Some(ParcelableStability::Local),
+            0b111111 => Some(ParcelableStability::Vintf),
+            _ => None,
+        }
+    }
+}
+
+pub trait ParcelableMetadata {
+    const DESCRIPTOR: &'static str;
+    const STABILITY: ParcelableStability = ParcelableStability::Local;
+}
+
+#[cfg(feature = "api-31")]
+struct RawParcel(Parcel);
+
+#[cfg(feature = "api-31")]
+unsafe impl Send for RawParcel {}
+
+#[cfg(feature = "api-31")]
+type Writer = fn(&(dyn Any + Send + Sync), &mut Parcel) -> Result<(), Status>;
+
+#[cfg(feature = "api-31")]
+fn write_any<T: Write + 'static>(value: &(dyn Any + Send + Sync), parcel: &mut Parcel) -> Result<(), Status> {
+    value.downcast_ref::<T>().unwrap().write(parcel)
+}
+
+#[cfg(feature = "api-31")]
+enum Contents {
+    Empty,
+    Parcelable(&'static str, Arc<dyn Any + Send + Sync>, Writer),
+    Parcel(RawParcel),
+}
+
+#[cfg(feature = "api-31")]
+pub struct ParcelableHolder {
+    stability: ParcelableStability,
+    contents: Mutex<Contents>,
+}
+
+#[cfg(feature = "api-31")]
+impl ParcelableHolder {
+    pub fn new(stability: ParcelableStability) -> Self {
+        ParcelableHolder {
+            stability,
+            contents: Mutex::new(Contents::Empty),
+        }
+    }
+
+    pub fn stability(&self) -> ParcelableStability {
+        self.stability
+    }
+
+    pub fn reset(&mut self) {
+        *self.contents.get_mut().unwrap() = Contents::Empty;
+    }
+
+    pub fn has_contents(&self) -> bool {
+        !matches!(*self.contents.lock().unwrap(), Contents::Empty)
+    }
+
+    pub fn set_parcelable<T>(&mut self, parcelable: Arc<T>) -> Result<(), Status>
+    where
+        T: ParcelableMetadata + Write + Send + Sync + 'static,
+    {
+        if self.stability == ParcelableStability::Vintf && T::STABILITY != ParcelableStability::Vintf {
+            return Err(Status::bad_value());
+        }
+
+        *self.contents.get_mut().unwrap() = Contents::Parcelable(T::DESCRIPTOR, parcelable, write_any::<T>);
+
+        Ok(())
+    }
+
+    pub fn get_parcelable<T>(&self) -> Result<Option<Arc<T>>, Status>
+    where
+        T: ParcelableMetadata + Read + Write + Send + Sync + 'static,
+    {
+        let mut contents = self.contents.lock().unwrap();
+
+        let value = match &*contents {
+            Contents::Empty => return Ok(None),
+            Contents::Parcelable(descriptor, value, _) => {
+                if *descriptor != T::DESCRIPTOR {
+                    return Ok(None);
+                }
+
+                return Ok(Some(value.clone().downcast::<T>().map_err(|_| Status::bad_value())?));
+            }
+            Contents::Parcel(RawParcel(data)) => {
+                unsafe { Status::from_raw_status_code(AParcel_setDataPosition(data.as_raw(), 0)).err(|| ())? };
+
+                if data.read::<String>()? != T::DESCRIPTOR {
+                    return Ok(None);
+                }
+
+                let mut body = Parcel::new();
+                body.write(&1i32)?;
+                body.append_from(
+                    data,
+                    data.get_data_position(),
+                    data.get_data_size() - data.get_data_position(),
+                )?;
+                body.set_data_position(0)?;
+
+                Arc::new(body.read::<T>()?)
+            }
+        };
+
+        *contents = Contents::Parcelable(T::DESCRIPTOR, value.clone(), write_any::<T>);
+
+        Ok(Some(value))
+    }
+}
+
+#[cfg(feature = "api-31")]
+impl Default for ParcelableHolder {
+    fn default() -> Self {
+        ParcelableHolder::new(ParcelableStability::Local)
+    }
+}
+
+#[cfg(feature = "api-31")]
+impl Debug for ParcelableHolder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let contents = match &*self.contents.lock().unwrap() {
+            Contents::Empty => "empty".to_string(),
+            Contents::Parcelable(descriptor, _, _) => descriptor.to_string(),
+            Contents::Parcel(RawParcel(data)) => format!("{} bytes", data.get_data_size()),
+        };
+
+        f.write_fmt(format_args!("ParcelableHolder({:?}, {})", self.stability, contents))
+    }
+}
+
+#[cfg(feature = "api-31")]
+impl Read for ParcelableHolder {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        if parcel.read::<i32>()? == 0 {
+            return Err(Status::with_code(Code::UnexpectedNull));
+        }
+
+        let stability = ParcelableStability::from_raw(parcel.read()?).ok_or_else(Status::bad_value)?;
+
+        let size = parcel.read::<i32>()?;
+        if size < 0 {
+            return Err(Status::bad_value());
+        }
+
+        if size == 0 {
+            return Ok(ParcelableHolder::new(stability));
+        }
+
+        let start = parcel.get_data_position();
+        let end = start
+            .checked_add(size as u32)
+            .filter(|end| *end <= i32::MAX as u32)
+            .ok_or_else(Status::bad_value)?;
+
+        let mut data = Parcel::new();
+        data.append_from(parcel, start, size as u32)?;
+
+        unsafe { Status::from_raw_status_code(AParcel_setDataPosition(parcel.as_raw(), end as i32)).err(|| ())? };
+
+        Ok(ParcelableHolder {
+            stability,
+            contents: Mutex::new(Contents::Parcel(RawParcel(data))),
+        })
+    }
+}
+
+#[cfg(feature = "api-31")]
+impl Write for ParcelableHolder {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write(&1i32)?;
+        parcel.write(&self.stability.as_raw())?;
+
+        match &*self.contents.lock().unwrap() {
+            Contents::Empty => parcel.write(&0i32),
+            Contents::Parcel(RawParcel(data)) => {
+                parcel.write(&(data.get_data_size() as i32))?;
+                parcel.append_from(data, 0, data.get_data_size())
+            }
+            Contents::Parcelable(descriptor, value, writer) => {
+                let mut body = Parcel::new();
+                writer(value.as_ref(), &mut body)?;
+
+                let size_position = parcel.get_data_position();
+                parcel.write(&0i32)?;
+
+                let start = parcel.get_data_position();
+                parcel.write(descriptor)?;
+                parcel.append_from(&body, 4, body.get_data_size() - 4)?;
+                let end = parcel.get_data_position();
+
+                parcel.set_data_position(size_position)?;
+                parcel.write(&((end - start) as i32))?;
+                parcel.set_data_position(end)
+            }
+        }
+    }
+}
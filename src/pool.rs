@@ -0,0 +1,81 @@
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex, OnceLock,
+    },
+    thread,
+};
+
+pub(crate) type Job = Box<dyn FnOnce() + Send>;
+
+const MAX_POOL_THREADS: usize = 16;
+
+/// The pool behind `transact_async` and `transact_with_timeout`, at most 16 threads in total.
+pub(crate) fn pool() -> &'static Pool {
+    static POOL: OnceLock<Pool> = OnceLock::new();
+
+    POOL.get_or_init(|| Pool::new("binder-pool", MAX_POOL_THREADS))
+}
+
+pub(crate) struct Pool {
+    name: &'static str,
+    sender: Mutex<Sender<Job>>,
+    receiver: Arc<Mutex<Receiver<Job>>>,
+    idle: Arc<AtomicUsize>,
+    threads: AtomicUsize,
+    max_threads: usize,
+}
+
+impl Pool {
+    pub fn new(name: &'static str, max_threads: usize) -> Pool {
+        let (sender, receiver) = channel();
+
+        Pool {
+            name,
+            sender: Mutex::new(sender),
+            receiver: Arc::new(Mutex::new(receiver)),
+            idle: Arc::new(AtomicUsize::new(0)),
+            threads: AtomicUsize::new(0),
+            max_threads,
+        }
+    }
+
+    pub fn execute(&self, job: Job) -> io::Result<()> {
+        let reserved = self.idle.load(Ordering::SeqCst) == 0
+            && self
+                .threads
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                    (n < self.max_threads).then_some(n + 1)
+                })
+                .is_ok();
+
+        if reserved {
+            let receiver = self.receiver.clone();
+            let idle = self.idle.clone();
+
+            let spawned = thread::Builder::new().name(self.name.to_string()).spawn(move || loop {
+                idle.fetch_add(1, Ordering::SeqCst);
+                let job = receiver.lock().unwrap().recv();
+                idle.fetch_sub(1, Ordering::SeqCst);
+
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => break,
+                }
+            });
+
+            if let Err(err) = spawned {
+                // without any thread the job would never run
+                if self.threads.fetch_sub(1, Ordering::SeqCst) == 1 {
+                    return Err(err);
+                }
+            }
+        }
+
+        self.sender.lock().unwrap().send(job).unwrap();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "api-31")]
+use std::os::fd::RawFd;
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+};
+
+#[cfg(feature = "api-31")]
+use crate::sys::binder_status_t;
+use crate::{symbol::Symbol, Status, SymbolNotFound};
+
+pub enum ProcessError {
+    SymbolNotFound(SymbolNotFound),
+    Binder(Status),
+}
+
+impl Debug for ProcessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessError::SymbolNotFound(err) => Debug::fmt(err, f),
+            ProcessError::Binder(status) => f.write_fmt(format_args!("binder error: {:?}", status)),
+        }
+    }
+}
+
+impl Display for ProcessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessError::SymbolNotFound(err) => Display::fmt(err, f),
+            ProcessError::Binder(status) => f.write_fmt(format_args!("binder error: {:?}", status)),
+        }
+    }
+}
+
+impl Error for ProcessError {}
+
+impl From<SymbolNotFound> for ProcessError {
+    fn from(value: SymbolNotFound) -> Self {
+        ProcessError::SymbolNotFound(value)
+    }
+}
+
+pub struct BinderProcess;
+
+impl BinderProcess {
+    pub fn start_thread_pool() -> Result<(), ProcessError> {
+        static FUNC: Symbol<unsafe extern "C" fn()> = unsafe { Symbol::new(c"ABinderProcess_startThreadPool") };
+
+        let func = FUNC.get()?;
+
+        unsafe { func() };
+
+        Ok(())
+    }
+
+    pub fn set_thread_pool_max_thread_count(count: u32) -> Result<bool, ProcessError> {
+        static FUNC: Symbol<unsafe extern "C" fn(u32) -> bool> =
+            unsafe { Symbol::new(c"ABinderProcess_setThreadPoolMaxThreadCount") };
+
+        let func = FUNC.get()?;
+
+        Ok(unsafe { func(count) })
+    }
+
+    pub fn join_thread_pool() -> Result<(), ProcessError> {
+        static FUNC: Symbol<unsafe extern "C" fn()> = unsafe { Symbol::new(c"ABinderProcess_joinThreadPool") };
+
+        let func = FUNC.get()?;
+
+        unsafe { func() };
+
+        Ok(())
+    }
+
+    #[cfg(feature = "api-31")]
+    pub fn setup_polling() -> Result<RawFd, ProcessError> {
+        static FUNC: Symbol<unsafe extern "C" fn(*mut RawFd) -> binder_status_t> =
+            unsafe { Symbol::new(c"ABinderProcess_setupPolling") };
+
+        let func = FUNC.get()?;
+
+        let mut fd: RawFd = -1;
+
+        Status::from_raw_status_code(unsafe { func(&mut fd) })
+            .err(|| fd)
+            .map_err(ProcessError::Binder)
+    }
+
+    #[cfg(feature = "api-31")]
+    pub fn handle_polled_commands() -> Result<(), ProcessError> {
+        static FUNC: Symbol<unsafe extern "C" fn() -> binder_status_t> =
+            unsafe { Symbol::new(c"ABinderProcess_handlePolledCommands") };
+
+        let func = FUNC.get()?;
+
+        Status::from_raw_status_code(unsafe { func() })
+            .err(|| ())
+            .map_err(ProcessError::Binder)
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{symbol::Symbol, sys::AParcel, Code, Parcel, Status};
+
+impl Parcel {
+    pub fn mark_sensitive(&self) -> Result<(), Status> {
+        static FUNC: Symbol<unsafe extern "C" fn(*const AParcel)> = unsafe { Symbol::new(c"AParcel_markSensitive") };
+
+        let func = FUNC.get().map_err(|_| Status::with_code(Code::InvalidOperation))?;
+
+        unsafe { func(self.as_raw()) };
+
+        Ok(())
+    }
+}
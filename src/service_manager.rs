@@ -2,12 +2,12 @@ use std::{
     error::Error,
     ffi::{c_char, CString, NulError},
     fmt::{Debug, Display, Formatter},
-    sync::OnceLock,
 };
 
 use crate::{
+    symbol::Symbol,
     sys::{binder_exception_t, AIBinder},
-    Exception, IBinder,
+    Exception, IBinder, SymbolNotFound,
 };
 
 pub enum ServiceManagerError {
@@ -38,19 +38,23 @@ impl Display for ServiceManagerError {
 
 impl Error for ServiceManagerError {}
 
+impl From<SymbolNotFound> for ServiceManagerError {
+    fn from(value: SymbolNotFound) -> Self {
+        ServiceManagerError::SymbolNotFound(value.name())
+    }
+}
+
+type GetService = unsafe extern "C" fn(*const c_char) -> *mut AIBinder;
+
 pub struct ServiceManager;
 
 impl ServiceManager {
-    fn get_or_check_service(
-        func: &OnceLock<unsafe extern "C" fn(*const c_char) -> *mut AIBinder>,
-        func_name: &[u8],
-        instance: &str,
-    ) -> Result<Option<IBinder>, ServiceManagerError> {
-        unsafe {
-            let func = func.get_or_init(|| std::mem::transmute(libc::dlsym(libc::RTLD_DEFAULT, func_name.as_ptr().cast())));
+    fn get_or_check_service(func: &Symbol<GetService>, instance: &str) -> Result<Option<IBinder>, ServiceManagerError> {
+        let func = func.get()?;
 
-            let instance = CString::new(instance).map_err(|err| ServiceManagerError::InvalidString(err))?;
+        let instance = CString::new(instance).map_err(|err| ServiceManagerError::InvalidString(err))?;
 
+        unsafe {
             let ptr = func(instance.as_ptr());
             if ptr.is_null() {
                 Ok(None)
@@ -61,33 +65,28 @@ impl ServiceManager {
     }
 
     pub fn get_service(instance: &str) -> Result<Option<IBinder>, ServiceManagerError> {
-        static FUNC: OnceLock<unsafe extern "C" fn(*const c_char) -> *mut AIBinder> = OnceLock::new();
+        static FUNC: Symbol<GetService> = unsafe { Symbol::new(c"AServiceManager_getService") };
 
-        Self::get_or_check_service(&FUNC, "AServiceManager_getService\0".as_bytes(), instance)
+        Self::get_or_check_service(&FUNC, instance)
     }
 
     pub fn check_service(instance: &str) -> Result<Option<IBinder>, ServiceManagerError> {
-        static FUNC: OnceLock<unsafe extern "C" fn(*const c_char) -> *mut AIBinder> = OnceLock::new();
+        static FUNC: Symbol<GetService> = unsafe { Symbol::new(c"AServiceManager_checkService") };
 
-        Self::get_or_check_service(&FUNC, "AServiceManager_checkService\0".as_bytes(), instance)
+        Self::get_or_check_service(&FUNC, instance)
     }
 
     pub fn add_service(instance: &str, binder: &IBinder) -> Result<(), ServiceManagerError> {
-        unsafe {
-            static FUNC: OnceLock<unsafe extern "C" fn(*mut AIBinder, *const c_char) -> binder_exception_t> = OnceLock::new();
-            let func = FUNC.get_or_init(|| {
-                std::mem::transmute(libc::dlsym(
-                    libc::RTLD_DEFAULT,
-                    "AServiceManager_addService\0".as_ptr().cast(),
-                ))
-            });
-
-            let instance = CString::new(instance).map_err(|err| ServiceManagerError::InvalidString(err))?;
-
-            match Exception::from_raw_exception(func(binder.as_raw(), instance.as_ptr())) {
-                Exception::None => Ok(()),
-                ex => Err(ServiceManagerError::RemoteException(ex)),
-            }
+        static FUNC: Symbol<unsafe extern "C" fn(*mut AIBinder, *const c_char) -> binder_exception_t> =
+            unsafe { Symbol::new(c"AServiceManager_addService") };
+
+        let func = FUNC.get()?;
+
+        let instance = CString::new(instance).map_err(|err| ServiceManagerError::InvalidString(err))?;
+
+        match Exception::from_raw_exception(unsafe { func(binder.as_raw(), instance.as_ptr()) }) {
+            Exception::None => Ok(()),
+            ex => Err(ServiceManagerError::RemoteException(ex)),
         }
     }
 }
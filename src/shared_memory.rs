@@ -0,0 +1,419 @@
+use std::{
+    error::Error,
+    ffi::{CStr, CString, NulError},
+    fmt::{Debug, Display, Formatter},
+    io,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd},
+    ptr::NonNull,
+};
+
+use crate::{Parcel, Read, ReadArray, Status, Write, WriteArray};
+
+pub enum SharedMemoryError {
+    InvalidString(NulError),
+    Io(io::Error),
+}
+
+impl Debug for SharedMemoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SharedMemoryError::InvalidString(err) => f.write_fmt(format_args!("invalid string: {:?}", err)),
+            SharedMemoryError::Io(err) => f.write_fmt(format_args!("io error: {:?}", err)),
+        }
+    }
+}
+
+impl Display for SharedMemoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SharedMemoryError::InvalidString(err) => f.write_fmt(format_args!("invalid string: {}", err)),
+            SharedMemoryError::Io(err) => f.write_fmt(format_args!("io error: {}", err)),
+        }
+    }
+}
+
+impl Error for SharedMemoryError {}
+
+impl From<io::Error> for SharedMemoryError {
+    fn from(value: io::Error) -> Self {
+        SharedMemoryError::Io(value)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Protection {
+    ReadOnly,
+    ReadWrite,
+}
+
+pub struct SharedMemory {
+    fd: OwnedFd,
+    size: usize,
+    protection: Protection,
+}
+
+impl SharedMemory {
+    pub fn create(name: &str, size: usize) -> Result<Self, SharedMemoryError> {
+        let name = CString::new(name).map_err(SharedMemoryError::InvalidString)?;
+
+        Ok(SharedMemory {
+            fd: platform::create(&name, size)?,
+            size,
+            protection: Protection::ReadWrite,
+        })
+    }
+
+    pub fn from_fd(fd: OwnedFd) -> Result<Self, SharedMemoryError> {
+        let size = platform::size(fd.as_fd())?;
+        let protection = platform::protection(fd.as_fd())?;
+
+        Ok(SharedMemory { fd, size, protection })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn protection(&self) -> Protection {
+        self.protection
+    }
+
+    pub fn seal_read_only(&mut self) -> Result<(), SharedMemoryError> {
+        if self.protection != Protection::ReadOnly {
+            platform::seal_read_only(self.fd.as_fd())?;
+
+            self.protection = Protection::ReadOnly;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), SharedMemoryError> {
+        if platform::size(self.fd.as_fd())? < self.size {
+            return Err(SharedMemoryError::Io(io::Error::from_raw_os_error(libc::EINVAL)));
+        }
+
+        Ok(())
+    }
+
+    /// Only memfd sealed against shrinking and writing can be mapped safely, ashmem always fails with `EPERM`. A
+    /// read-only ashmem protection mask does not revoke writable mappings made before it, use `map_unchecked` for ashmem.
+    pub fn map(&self) -> Result<Mapping<'_>, SharedMemoryError> {
+        let seals = memfd::seals(self.fd.as_fd()).unwrap_or(0);
+        if seals & libc::F_SEAL_SHRINK == 0 || seals & libc::F_SEAL_WRITE == 0 {
+            return Err(SharedMemoryError::Io(io::Error::from_raw_os_error(libc::EPERM)));
+        }
+
+        unsafe { self.map_unchecked() }
+    }
+
+    /// # Safety
+    ///
+    /// No other process or mapping may shrink or write to the memory while the mapping is alive.
+    pub unsafe fn map_unchecked(&self) -> Result<Mapping<'_>, SharedMemoryError> {
+        self.validate()?;
+
+        Ok(Mapping {
+            region: Region::new(self.fd.as_fd(), self.size, libc::PROT_READ)?,
+            _memory: PhantomData,
+        })
+    }
+
+    /// # Safety
+    ///
+    /// No other process or mapping may shrink, read or write the memory while the mapping is alive.
+    pub unsafe fn map_mut(&mut self) -> Result<MappingMut<'_>, SharedMemoryError> {
+        if self.protection == Protection::ReadOnly {
+            return Err(SharedMemoryError::Io(io::Error::from_raw_os_error(libc::EPERM)));
+        }
+
+        self.validate()?;
+
+        Ok(MappingMut {
+            region: Region::new(self.fd.as_fd(), self.size, libc::PROT_READ | libc::PROT_WRITE)?,
+            _memory: PhantomData,
+        })
+    }
+}
+
+impl Debug for SharedMemory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "SharedMemory(fd={}, size={}, protection={:?})",
+            self.fd.as_raw_fd(),
+            self.size,
+            self.protection
+        ))
+    }
+}
+
+impl AsFd for SharedMemory {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl From<SharedMemory> for OwnedFd {
+    fn from(value: SharedMemory) -> Self {
+        value.fd
+    }
+}
+
+struct Region {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+impl Region {
+    fn new(fd: BorrowedFd, len: usize, prot: libc::c_int) -> io::Result<Self> {
+        if len == 0 {
+            return Ok(Region {
+                ptr: NonNull::dangling(),
+                len,
+            });
+        }
+
+        unsafe {
+            let ptr = libc::mmap(std::ptr::null_mut(), len, prot, libc::MAP_SHARED, fd.as_raw_fd(), 0);
+            if ptr == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Region {
+                ptr: NonNull::new_unchecked(ptr.cast()),
+                len,
+            })
+        }
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.len) };
+        }
+    }
+}
+
+pub struct Mapping<'a> {
+    region: Region,
+    _memory: PhantomData<&'a SharedMemory>,
+}
+
+impl<'a> Deref for Mapping<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::slice::from_raw_parts(self.region.ptr.as_ptr(), self.region.len) }
+    }
+}
+
+pub struct MappingMut<'a> {
+    region: Region,
+    _memory: PhantomData<&'a mut SharedMemory>,
+}
+
+impl<'a> Deref for MappingMut<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::slice::from_raw_parts(self.region.ptr.as_ptr(), self.region.len) }
+    }
+}
+
+impl<'a> DerefMut for MappingMut<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { std::slice::from_raw_parts_mut(self.region.ptr.as_ptr(), self.region.len) }
+    }
+}
+
+mod memfd {
+    use super::*;
+
+    pub fn create(name: &CStr, size: usize) -> io::Result<OwnedFd> {
+        unsafe {
+            let fd = libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let fd = OwnedFd::from_raw_fd(fd);
+            if libc::ftruncate(fd.as_raw_fd(), size as libc::off_t) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(fd)
+        }
+    }
+
+    pub fn seals(fd: BorrowedFd) -> Option<libc::c_int> {
+        let seals = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GET_SEALS) };
+
+        (seals >= 0).then_some(seals)
+    }
+
+    pub fn size(fd: BorrowedFd) -> io::Result<usize> {
+        unsafe {
+            let mut stat: libc::stat = std::mem::zeroed();
+            if libc::fstat(fd.as_raw_fd(), &mut stat) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(stat.st_size as usize)
+        }
+    }
+
+    pub fn protection(seals: libc::c_int) -> Protection {
+        if seals & libc::F_SEAL_WRITE != 0 {
+            Protection::ReadOnly
+        } else {
+            Protection::ReadWrite
+        }
+    }
+
+    pub fn seal_read_only(fd: BorrowedFd) -> io::Result<()> {
+        let seals = libc::F_SEAL_WRITE | libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_SEAL;
+
+        if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_ADD_SEALS, seals) } < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+mod platform {
+    use super::*;
+
+    pub fn create(name: &CStr, size: usize) -> io::Result<OwnedFd> {
+        memfd::create(name, size)
+    }
+
+    pub fn size(fd: BorrowedFd) -> io::Result<usize> {
+        memfd::size(fd)
+    }
+
+    pub fn protection(fd: BorrowedFd) -> io::Result<Protection> {
+        Ok(memfd::seals(fd).map_or(Protection::ReadWrite, memfd::protection))
+    }
+
+    pub fn seal_read_only(fd: BorrowedFd) -> io::Result<()> {
+        memfd::seal_read_only(fd)
+    }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+    use std::ffi::c_char;
+
+    use super::*;
+    use crate::symbol::Symbol;
+
+    const ASHMEM_GET_PROT_MASK: libc::c_int = 0x7706;
+
+    pub fn create(name: &CStr, size: usize) -> io::Result<OwnedFd> {
+        static FUNC: Symbol<unsafe extern "C" fn(*const c_char, usize) -> libc::c_int> =
+            unsafe { Symbol::new(c"ASharedMemory_create") };
+
+        let func = match FUNC.get() {
+            Ok(func) => func,
+            Err(_) => return memfd::create(name, size),
+        };
+
+        unsafe {
+            let fd = func(name.as_ptr(), size);
+            if fd < 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(OwnedFd::from_raw_fd(fd))
+            }
+        }
+    }
+
+    pub fn size(fd: BorrowedFd) -> io::Result<usize> {
+        static FUNC: Symbol<unsafe extern "C" fn(libc::c_int) -> usize> = unsafe { Symbol::new(c"ASharedMemory_getSize") };
+
+        if memfd::seals(fd).is_some() {
+            return memfd::size(fd);
+        }
+
+        let func = match FUNC.get() {
+            Ok(func) => func,
+            Err(_) => return memfd::size(fd),
+        };
+
+        Ok(unsafe { func(fd.as_raw_fd()) })
+    }
+
+    pub fn protection(fd: BorrowedFd) -> io::Result<Protection> {
+        if let Some(seals) = memfd::seals(fd) {
+            return Ok(memfd::protection(seals));
+        }
+
+        let mask = unsafe { libc::ioctl(fd.as_raw_fd(), ASHMEM_GET_PROT_MASK as _) };
+        if mask < 0 || mask & libc::PROT_WRITE != 0 {
+            Ok(Protection::ReadWrite)
+        } else {
+            Ok(Protection::ReadOnly)
+        }
+    }
+
+    pub fn seal_read_only(fd: BorrowedFd) -> io::Result<()> {
+        static FUNC: Symbol<unsafe extern "C" fn(libc::c_int, libc::c_int) -> libc::c_int> =
+            unsafe { Symbol::new(c"ASharedMemory_setProt") };
+
+        if memfd::seals(fd).is_some() {
+            return memfd::seal_read_only(fd);
+        }
+
+        let func = match FUNC.get() {
+            Ok(func) => func,
+            Err(_) => return Err(io::Error::from_raw_os_error(libc::ENOSYS)),
+        };
+
+        if unsafe { func(fd.as_raw_fd(), libc::PROT_READ) } < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Read for SharedMemory {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        SharedMemory::from_fd(parcel.read()?).map_err(|_| Status::bad_value())
+    }
+}
+
+impl Read for Option<SharedMemory> {
+    fn read(parcel: &Parcel) -> Result<Self, Status> {
+        parcel
+            .read::<Option<OwnedFd>>()?
+            .map(SharedMemory::from_fd)
+            .transpose()
+            .map_err(|_| Status::bad_value())
+    }
+}
+
+impl Write for SharedMemory {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write(&self.fd.as_fd())
+    }
+}
+
+impl Write for Option<SharedMemory> {
+    fn write(&self, parcel: &mut Parcel) -> Result<(), Status> {
+        parcel.write(&self.as_ref().map(|m| m.fd.as_fd()))
+    }
+}
+
+impl ReadArray for SharedMemory {}
+
+impl WriteArray for SharedMemory {}
+
+impl ReadArray for Option<SharedMemory> {}
+
+impl WriteArray for Option<SharedMemory> {}
@@ -0,0 +1,71 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+};
+
+use crate::{symbol::Symbol, sys::AIBinder, IBinder, SymbolNotFound};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Stability {
+    Local,
+    Vendor,
+    System,
+    Vintf,
+}
+
+pub enum StabilityError {
+    SymbolNotFound(SymbolNotFound),
+    RemoteBinder,
+}
+
+impl Debug for StabilityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StabilityError::SymbolNotFound(err) => Debug::fmt(err, f),
+            StabilityError::RemoteBinder => f.write_str("stability of remote binder can not be changed"),
+        }
+    }
+}
+
+impl Display for StabilityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StabilityError::SymbolNotFound(err) => Display::fmt(err, f),
+            StabilityError::RemoteBinder => f.write_str("stability of remote binder can not be changed"),
+        }
+    }
+}
+
+impl Error for StabilityError {}
+
+impl From<SymbolNotFound> for StabilityError {
+    fn from(value: SymbolNotFound) -> Self {
+        StabilityError::SymbolNotFound(value)
+    }
+}
+
+type MarkStability = unsafe extern "C" fn(*mut AIBinder);
+
+impl IBinder {
+    pub fn mark_stability(&self, stability: Stability) -> Result<(), StabilityError> {
+        static LOCAL: Symbol<MarkStability> = unsafe { Symbol::new(c"AIBinder_forceDowngradeToLocalStability") };
+        static VENDOR: Symbol<MarkStability> = unsafe { Symbol::new(c"AIBinder_markVendorStability") };
+        static SYSTEM: Symbol<MarkStability> = unsafe { Symbol::new(c"AIBinder_markSystemStability") };
+        static VINTF: Symbol<MarkStability> = unsafe { Symbol::new(c"AIBinder_markVintfStability") };
+
+        if self.is_remote() {
+            return Err(StabilityError::RemoteBinder);
+        }
+
+        let func = match stability {
+            Stability::Local => LOCAL.get()?,
+            Stability::Vendor => VENDOR.get()?,
+            Stability::System => SYSTEM.get()?,
+            Stability::Vintf => VINTF.get()?,
+        };
+
+        unsafe { func(self.as_raw()) };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    error::Error,
+    ffi::{c_void, CStr},
+    fmt::{Debug, Display, Formatter},
+    marker::PhantomData,
+    sync::OnceLock,
+};
+
+pub struct SymbolNotFound(&'static str);
+
+impl SymbolNotFound {
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Debug for SymbolNotFound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("symbol {:?} not found", self.0))
+    }
+}
+
+impl Display for SymbolNotFound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("symbol {} not found", self.0))
+    }
+}
+
+impl Error for SymbolNotFound {}
+
+pub(crate) struct Symbol<F> {
+    name: &'static CStr,
+    ptr: OnceLock<usize>,
+    _func: PhantomData<F>,
+}
+
+impl<F: Copy> Symbol<F> {
+    /// # Safety
+    ///
+    /// `F` must be a function pointer type matching the signature of the symbol called `name`.
+    pub const unsafe fn new(name: &'static CStr) -> Self {
+        Symbol {
+            name,
+            ptr: OnceLock::new(),
+            _func: PhantomData,
+        }
+    }
+
+    pub fn get(&self) -> Result<F, SymbolNotFound> {
+        let ptr = *self.ptr.get_or_init(|| unsafe { lookup(self.name) as usize });
+        if ptr == 0 {
+            Err(SymbolNotFound(self.name.to_str().unwrap_or_default()))
+        } else {
+            unsafe { Ok(std::mem::transmute_copy::<*mut c_void, F>(&(ptr as *mut c_void))) }
+        }
+    }
+}
+
+#[cfg(not(feature = "host-fake"))]
+unsafe fn lookup(name: &CStr) -> *mut c_void {
+    libc::dlsym(libc::RTLD_DEFAULT, name.as_ptr())
+}
+
+#[cfg(feature = "host-fake")]
+unsafe fn lookup(name: &CStr) -> *mut c_void {
+    crate::sys::dlsym(name.as_ptr())
+}
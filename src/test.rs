@@ -149,6 +149,426 @@ fn parcel_parcelable_array() {
     }
 }
 
+#[test]
+#[cfg(feature = "api-31")]
+fn parcel_nested_array() {
+    for _ in 0..100 {
+        let nested: Vec<Vec<i32>> = Faker.fake();
+        let bytes: Option<Vec<Option<Vec<u8>>>> = Faker.fake();
+        let fixed: [[i64; 3]; 2] = Faker.fake();
+        let nullable_fixed: Option<[bool; 4]> = Faker.fake();
+        let strings: Vec<Option<Vec<Option<String>>>> = Faker.fake();
+
+        let mut parcel = Parcel::new();
+        parcel.write(&nested).unwrap();
+        parcel.write(&bytes).unwrap();
+        parcel.write(&fixed).unwrap();
+        parcel.write(&nullable_fixed).unwrap();
+        parcel.write(&strings.as_slice()).unwrap();
+
+        parcel.set_data_position(0).unwrap();
+
+        assert_eq!(parcel.read::<Vec<Vec<i32>>>().unwrap(), nested);
+        assert_eq!(parcel.read::<Option<Vec<Option<Vec<u8>>>>>().unwrap(), bytes);
+        assert_eq!(parcel.read::<[[i64; 3]; 2]>().unwrap(), fixed);
+        assert_eq!(parcel.read::<Option<[bool; 4]>>().unwrap(), nullable_fixed);
+        assert_eq!(parcel.read::<Vec<Option<Vec<Option<String>>>>>().unwrap(), strings);
+    }
+
+    let mut parcel = Parcel::new();
+    parcel.write(&[1u8, 2, 3]).unwrap();
+    parcel.write(&None::<Vec<i32>>).unwrap();
+    parcel.write(&std::vec![1i32, 2]).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<Option<Vec<i8>>>().unwrap(), Some(std::vec![1, 2, 3]));
+    assert!(matches!(parcel.read::<Vec<i32>>(), Err(s) if s.get_code() == Code::UnexpectedNull));
+    assert!(matches!(parcel.read::<[i32; 3]>(), Err(s) if s.get_code() == Code::BadValue));
+}
+
+#[test]
+#[cfg(feature = "api-31")]
+fn parcel_tuple() {
+    type Wide = (
+        bool,
+        i8,
+        u16,
+        i32,
+        u32,
+        i64,
+        u64,
+        f32,
+        f64,
+        Option<String>,
+        Vec<i32>,
+        Option<Vec<Option<String>>>,
+    );
+
+    for _ in 0..100 {
+        let value: Wide = Faker.fake();
+        let pair: (String, i32) = Faker.fake();
+
+        let mut parcel = Parcel::new();
+        parcel.write(&value).unwrap();
+        parcel.write(&()).unwrap();
+        parcel.write(&pair.0).unwrap();
+        parcel.write(&pair.1).unwrap();
+
+        parcel.set_data_position(0).unwrap();
+
+        assert_eq!(parcel.read::<Wide>().unwrap(), value);
+        parcel.read::<()>().unwrap();
+        assert_eq!(parcel.read::<(String, i32)>().unwrap(), pair);
+        assert_eq!(parcel.get_data_position(), parcel.get_data_size());
+    }
+
+    let mut parcel = Parcel::new();
+    parcel.write(&()).unwrap();
+
+    assert_eq!(parcel.get_data_size(), 0);
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "derive"))]
+fn parcel_derive() {
+    #[derive(Debug, Default, PartialEq, Dummy, Read, Write)]
+    struct Inner(i32, Option<String>);
+
+    #[derive(Debug, Default, PartialEq, Dummy, Read, Write)]
+    struct Outer {
+        id: i64,
+        inner: Inner,
+        nullable: Option<Inner>,
+        tags: Option<Vec<Option<String>>>,
+    }
+
+    #[derive(Debug, Default, PartialEq, Dummy, Read, Write)]
+    struct OuterV2 {
+        id: i64,
+        inner: Inner,
+        nullable: Option<Inner>,
+        tags: Option<Vec<Option<String>>>,
+        extra: u64,
+    }
+
+    for _ in 0..100 {
+        let v2: OuterV2 = Faker.fake();
+        let tail: i32 = Faker.fake();
+
+        let mut parcel = Parcel::new();
+        parcel.write(&v2).unwrap();
+        parcel.write(&tail).unwrap();
+
+        parcel.set_data_position(0).unwrap();
+
+        let v1: Outer = parcel.read().unwrap();
+
+        assert_eq!(v1.id, v2.id);
+        assert_eq!(v1.inner, v2.inner);
+        assert_eq!(v1.nullable, v2.nullable);
+        assert_eq!(v1.tags, v2.tags);
+        assert_eq!(tail, parcel.read::<i32>().unwrap());
+
+        let mut parcel = Parcel::new();
+        parcel.write(&v1).unwrap();
+        parcel.write(&tail).unwrap();
+
+        parcel.set_data_position(0).unwrap();
+
+        let v2: OuterV2 = parcel.read().unwrap();
+
+        assert_eq!(v2.id, v1.id);
+        assert_eq!(v2.inner, v1.inner);
+        assert_eq!(v2.nullable, v1.nullable);
+        assert_eq!(v2.tags, v1.tags);
+        assert_eq!(v2.extra, 0);
+        assert_eq!(tail, parcel.read::<i32>().unwrap());
+    }
+
+    let mut parcel = Parcel::new();
+    parcel.write::<Option<Outer>>(&None).unwrap();
+    parcel.write::<Option<Outer>>(&None).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<Option<Outer>>().unwrap(), None);
+    assert!(matches!(parcel.read::<Outer>(), Err(s) if s.get_code() == Code::UnexpectedNull));
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "derive"))]
+fn parcel_derive_enum() {
+    #[derive(Debug, Copy, Clone, PartialEq, Read, Write)]
+    #[repr(i32)]
+    enum Strict {
+        Red = 1,
+        Green,
+        Blue = 10,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Read, Write)]
+    #[repr(i8)]
+    enum Open {
+        Zero,
+        One,
+        Unknown(i8),
+    }
+
+    let mut parcel = Parcel::new();
+
+    parcel.write(&Strict::Green).unwrap();
+    parcel.write(&Some(std::vec![Strict::Red, Strict::Blue])).unwrap();
+    parcel.write(&Open::One).unwrap();
+    parcel.write(&Open::Unknown(42)).unwrap();
+    parcel.write(&Some(&[Open::Zero, Open::Unknown(-1)][..])).unwrap();
+    parcel.write(&3i32).unwrap();
+    parcel.write(&None::<Vec<Strict>>).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<i32>().unwrap(), 2);
+    assert_eq!(parcel.read::<Option<Vec<i32>>>().unwrap(), Some(std::vec![1, 10]));
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<Strict>().unwrap(), Strict::Green);
+    assert_eq!(
+        parcel.read::<Option<Vec<Strict>>>().unwrap(),
+        Some(std::vec![Strict::Red, Strict::Blue])
+    );
+    assert_eq!(parcel.read::<Open>().unwrap(), Open::One);
+    assert_eq!(parcel.read::<Open>().unwrap(), Open::Unknown(42));
+    assert_eq!(
+        parcel.read::<Option<Vec<Open>>>().unwrap(),
+        Some(std::vec![Open::Zero, Open::Unknown(-1)])
+    );
+    assert!(matches!(parcel.read::<Strict>(), Err(st) if st.get_code() == Code::BadValue));
+    assert_eq!(parcel.read::<Option<Vec<Strict>>>().unwrap(), None);
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "derive"))]
+fn parcel_derive_union() {
+    #[derive(Debug, PartialEq, Read, Write)]
+    enum Value {
+        Number(i32),
+        Text(Option<String>),
+        LongNumber(i64),
+    }
+
+    assert_eq!(Value::TAG_NUMBER, 0);
+    assert_eq!(Value::TAG_LONG_NUMBER, 2);
+    assert_eq!(Value::Text(None).tag(), Value::TAG_TEXT);
+
+    let text: Option<String> = Faker.fake();
+
+    let mut parcel = Parcel::new();
+
+    parcel.write(&Value::Number(114514)).unwrap();
+    parcel.write(&Value::Text(text.clone())).unwrap();
+    parcel.write(&None::<Value>).unwrap();
+    parcel.write(&Some(Value::LongNumber(-1))).unwrap();
+    parcel.write(&1i32).unwrap();
+    parcel.write(&3i32).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<Value>().unwrap(), Value::Number(114514));
+    assert_eq!(parcel.read::<Value>().unwrap(), Value::Text(text));
+    assert_eq!(parcel.read::<Option<Value>>().unwrap(), None);
+    assert_eq!(parcel.read::<Option<Value>>().unwrap(), Some(Value::LongNumber(-1)));
+    assert!(matches!(parcel.read::<Value>(), Err(st) if st.get_code() == Code::BadValue));
+}
+
+#[test]
+#[cfg(feature = "api-31")]
+fn parcel_bytes() {
+    for _ in 0..100 {
+        let data: Vec<u8> = Faker.fake();
+
+        let mut parcel = Parcel::new();
+        parcel.write_bytes(&data).unwrap();
+        parcel.write_bytes(&data).unwrap();
+        parcel.write(&data).unwrap();
+
+        parcel.set_data_position(0).unwrap();
+
+        let mut buffer = std::vec![0xff; 16];
+        parcel.read_bytes_into(&mut buffer).unwrap();
+        assert_eq!(buffer, data);
+        assert!(parcel.read_byte_slice_with(|s| s == data.as_slice()).unwrap());
+        assert_eq!(parcel.read::<Option<Vec<i8>>>().unwrap().map(|v| v.len()), Some(data.len()));
+    }
+
+    let mut parcel = Parcel::new();
+    parcel.write(&None::<Vec<u8>>).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    let mut buffer = Vec::new();
+    assert!(matches!(parcel.read_bytes_into(&mut buffer), Err(s) if s.get_code() == Code::UnexpectedNull));
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "bytes"))]
+fn parcel_bytes_integration() {
+    let data = bytes::Bytes::from_static(b"ndkbinder");
+
+    let mut parcel = Parcel::new();
+    parcel.write(&data).unwrap();
+    parcel.write(&None::<bytes::Bytes>).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<bytes::Bytes>().unwrap(), data);
+    assert_eq!(parcel.read::<Option<bytes::Bytes>>().unwrap(), None);
+}
+
+#[test]
+#[cfg(feature = "api-31")]
+fn parcel_non_null() {
+    struct Service;
+
+    impl Class for Service {
+        const INTERFACE_NAME: &'static str = "parcel_non_null";
+
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Ok(())
+        }
+    }
+
+    define_class!(Service);
+
+    let binder: IBinder = Service.into();
+    let fd = open_memfd();
+
+    for _ in 0..100 {
+        let text: String = Faker.fake();
+        let texts: Vec<String> = Faker.fake();
+
+        let mut parcel = Parcel::new();
+        parcel.write(&text).unwrap();
+        parcel.write(&texts.iter().map(String::as_str).collect::<Vec<_>>()).unwrap();
+        parcel.write(&binder).unwrap();
+        parcel.write(&std::vec![binder.clone(), binder.clone()]).unwrap();
+        parcel.write(&fd.as_fd()).unwrap();
+
+        parcel.set_data_position(0).unwrap();
+
+        assert_eq!(parcel.read::<String>().unwrap(), text);
+        assert_eq!(parcel.read::<Vec<String>>().unwrap(), texts);
+        assert_eq!(parcel.read::<IBinder>().unwrap(), binder);
+        assert_eq!(
+            parcel.read::<Vec<IBinder>>().unwrap(),
+            std::vec![binder.clone(), binder.clone()]
+        );
+        assert!(parcel.read::<OwnedFd>().unwrap().as_raw_fd() >= 0);
+    }
+
+    let mut parcel = Parcel::new();
+    parcel.write(&None::<&str>).unwrap();
+    parcel.write(&None::<IBinder>).unwrap();
+    parcel.write(&None::<OwnedFd>).unwrap();
+    parcel.write(&Some(std::vec![Some("a"), None])).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert!(matches!(parcel.read::<String>(), Err(s) if s.get_code() == Code::UnexpectedNull));
+    assert!(matches!(parcel.read::<IBinder>(), Err(s) if s.get_code() == Code::UnexpectedNull));
+    assert!(matches!(parcel.read::<OwnedFd>(), Err(s) if s.get_code() == Code::UnexpectedNull));
+    assert!(matches!(parcel.read::<Vec<String>>(), Err(s) if s.get_code() == Code::UnexpectedNull));
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "shared_memory"))]
+fn parcel_shared_memory() {
+    let data: Vec<u8> = Faker.fake();
+
+    let mut memory = SharedMemory::create("parcel_shared_memory", data.len()).unwrap();
+    assert!(memory.map().is_err());
+
+    unsafe { memory.map_mut() }.unwrap().copy_from_slice(&data);
+    memory.seal_read_only().unwrap();
+
+    assert_eq!(memory.protection(), Protection::ReadOnly);
+    assert!(unsafe { memory.map_mut() }.is_err());
+    assert_eq!(&*memory.map().unwrap(), data.as_slice());
+
+    let mut parcel = Parcel::new();
+    parcel.write(&memory).unwrap();
+    parcel.write(&None::<SharedMemory>).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    let r_memory: SharedMemory = parcel.read().unwrap();
+    assert_eq!(r_memory.size(), data.len());
+    assert_eq!(r_memory.protection(), Protection::ReadOnly);
+    assert_eq!(&*r_memory.map().unwrap(), data.as_slice());
+    assert!(parcel.read::<Option<SharedMemory>>().unwrap().is_none());
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "derive"))]
+fn parcel_parcelable_holder() {
+    #[derive(Debug, Default, PartialEq, Dummy, Read, Write)]
+    struct Extension {
+        id: i32,
+        name: Option<String>,
+    }
+
+    impl ParcelableMetadata for Extension {
+        const DESCRIPTOR: &'static str = "ndkbinder.Extension";
+    }
+
+    #[derive(Debug, Default, PartialEq, Read, Write)]
+    struct Other(i64);
+
+    impl ParcelableMetadata for Other {
+        const DESCRIPTOR: &'static str = "ndkbinder.Other";
+    }
+
+    let extension: Extension = Faker.fake();
+
+    let mut holder = ParcelableHolder::default();
+    holder.set_parcelable(std::sync::Arc::new(extension)).unwrap();
+
+    let mut parcel = Parcel::new();
+    parcel.write(&holder).unwrap();
+    parcel.write(&ParcelableHolder::new(ParcelableStability::Vintf)).unwrap();
+    parcel.write(&7i32).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    let r_holder: ParcelableHolder = parcel.read().unwrap();
+    let holder_size = parcel.get_data_position();
+    let empty: ParcelableHolder = parcel.read().unwrap();
+    assert_eq!(parcel.read::<i32>().unwrap(), 7);
+    assert!(!empty.has_contents());
+    assert_eq!(empty.stability(), ParcelableStability::Vintf);
+
+    let mut forwarded = Parcel::new();
+    forwarded.write(&r_holder).unwrap();
+    assert_eq!(forwarded.get_data_size(), holder_size);
+
+    #[cfg(feature = "api-33")]
+    {
+        let mut original = std::vec![0u8; holder_size as usize];
+        let mut copied = original.clone();
+        parcel.marshal(&mut original, 0).unwrap();
+        forwarded.marshal(&mut copied, 0).unwrap();
+        assert_eq!(original, copied);
+    }
+
+    assert!(r_holder.get_parcelable::<Other>().unwrap().is_none());
+    let r_extension = r_holder.get_parcelable::<Extension>().unwrap().unwrap();
+    assert_eq!(r_extension.id, holder.get_parcelable::<Extension>().unwrap().unwrap().id);
+    assert_eq!(r_extension.name, holder.get_parcelable::<Extension>().unwrap().unwrap().name);
+
+    let mut vintf = ParcelableHolder::new(ParcelableStability::Vintf);
+    assert!(matches!(vintf.set_parcelable(std::sync::Arc::new(Other(1))), Err(s) if s.get_code() == Code::BadValue));
+}
+
 #[test]
 #[cfg(feature = "api-31")]
 fn parcel_status() {
@@ -222,7 +642,12 @@ fn binder_local_transact() {
     let s: Option<String> = Faker.fake();
 
     let rs = binder
-        .transact(1, |data| data.write(&s), |reply| reply.unwrap().read::<Option<String>>(), &[])
+        .transact(
+            1,
+            |data| data.write(&s),
+            |reply| reply.unwrap().read::<Option<String>>(),
+            Flags::empty(),
+        )
         .unwrap();
 
     assert_eq!(s.map(|s| s + "114514"), rs);
@@ -230,77 +655,263 @@ fn binder_local_transact() {
     let v: u64 = Faker.fake();
 
     let rv = binder
-        .transact(2, |data| data.write(&v), |reply| reply.unwrap().read::<u64>(), &[])
+        .transact(2, |data| data.write(&v), |reply| reply.unwrap().read::<u64>(), Flags::empty())
         .unwrap();
 
     assert_eq!(v + 114514, rv);
 
-    let rn = binder.transact(3, |_| Ok(()), |_| Ok(()), &[]);
+    let rn = binder.transact(3, |_| Ok(()), |_| Ok(()), Flags::empty());
 
     assert!(matches!(rn, Err(st) if st.get_code() == Code::UnknownTransaction));
 }
 
-fn open_memfd() -> OwnedFd {
-    unsafe {
-        let func: unsafe extern "C" fn(name: *const std::ffi::c_char, flags: std::ffi::c_uint) -> RawFd =
-            std::mem::transmute(libc::dlsym(libc::RTLD_DEFAULT, "memfd_create\0".as_ptr().cast()));
+#[test]
+#[cfg(feature = "api-31")]
+fn binder_status_header() {
+    struct StatusService;
 
-        OwnedFd::from_raw_fd(func("file\0".as_ptr().cast(), 0))
+    impl Class for StatusService {
+        const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder";
+
+        fn enable_status_header() -> bool {
+            true
+        }
+
+        fn on_transact(&self, code: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            match code {
+                1 => {
+                    let v: i32 = data.read()?;
+
+                    reply.unwrap().write(&(v + 114514))
+                }
+                2 => {
+                    let msg: Option<String> = data.read()?;
+
+                    Err(Status::with_exception_and_message(Exception::Security, &msg.unwrap()).unwrap())
+                }
+                3 => {
+                    let error: i32 = data.read()?;
+
+                    Err(Status::with_service_specific_error(error))
+                }
+                5 => {
+                    let reply = reply.unwrap();
+                    reply.write(&[1i64; 16])?;
+
+                    Err(Status::with_exception(Exception::IllegalState))
+                }
+                _ => Err(Status::unknown_transaction()),
+            }
+        }
     }
+
+    define_class!(StatusService);
+
+    let binder: IBinder = StatusService.into();
+
+    let v: i32 = Faker.fake::<i16>() as i32;
+
+    let rv = binder
+        .transact_with_status_header(1, |data| data.write(&v), |reply| reply.unwrap().read::<i32>(), Flags::empty())
+        .unwrap();
+
+    assert_eq!(v + 114514, rv);
+
+    let msg: String = Faker.fake();
+
+    let rs = binder.transact_with_status_header(2, |data| data.write(&Some(msg.as_str())), |_| Ok(()), Flags::empty());
+
+    assert!(matches!(&rs, Err(st) if st.get_exception() == Exception::Security));
+    assert_eq!(rs.err().unwrap().get_message().unwrap(), Some(msg));
+
+    let error: i32 = Faker.fake();
+
+    let re = binder.transact_with_status_header(3, |data| data.write(&error), |_| Ok(()), Flags::empty());
+
+    assert!(
+        matches!(re, Err(st) if st.get_exception() == Exception::ServiceSpecific && st.get_service_specific_error() == error)
+    );
+
+    let (status, rest) = binder
+        .transact(
+            5,
+            |_| Ok(()),
+            |reply| {
+                let reply = reply.unwrap();
+
+                Ok((reply.read::<Status>()?, reply.get_data_size() - reply.get_data_position()))
+            },
+            Flags::empty(),
+        )
+        .unwrap();
+
+    assert_eq!(status.get_exception(), Exception::IllegalState);
+    assert_eq!(rest, 0);
+
+    let rn = binder.transact_with_status_header(4, |_| Ok(()), |_| Ok(()), Flags::empty());
+
+    assert!(matches!(rn, Err(st) if st.get_code() == Code::UnknownTransaction));
 }
 
 #[test]
-fn binder_dump() {
-    struct DumpService;
+#[cfg(feature = "api-31")]
+fn binder_downcast_local() {
+    struct LocalService(u64);
 
-    impl Class for DumpService {
+    impl Class for LocalService {
         const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder";
 
         fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
             Err(Status::unknown_transaction())
         }
+    }
 
-        fn on_dump(&self, fd: RawFd, args: &[&str]) -> Result<(), Status> {
-            let args = CString::new(args.join(",")).unwrap().into_bytes();
+    struct OtherService;
 
-            unsafe {
-                libc::write(fd, args.as_ptr().cast(), args.len());
-            }
+    impl Class for OtherService {
+        const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder";
 
-            Ok(())
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Err(Status::unknown_transaction())
         }
     }
 
-    define_class!(DumpService);
+    define_class!(LocalService);
+    define_class!(OtherService);
 
-    let binder: IBinder = DumpService.into();
+    let v: u64 = Faker.fake();
 
-    let fd = open_memfd();
-    let args: Vec<String> = Faker.fake();
-    let args = args.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+    let binder: IBinder = LocalService(v).into();
 
-    binder.dump(fd.as_raw_fd(), &args).unwrap();
+    assert_eq!(binder.downcast_local::<LocalService>().unwrap().0, v);
+    assert!(binder.downcast_local::<OtherService>().is_none());
 
-    let content = std::fs::read_to_string(format!("/proc/self/fd/{}", fd.as_raw_fd())).unwrap();
+    let mut parcel = Parcel::new();
 
-    assert_eq!(content, args.join(","));
+    parcel.write(&Some(&binder)).unwrap();
+    parcel.set_data_position(0).unwrap();
+
+    let r_binder: Option<IBinder> = parcel.read().unwrap();
+
+    assert_eq!(r_binder.unwrap().downcast_local::<LocalService>().unwrap().0, v);
 }
 
-#[cfg(feature = "service_manager")]
 #[test]
-fn binder_dump_sys_service() {
-    use std::io::Read;
+#[cfg(feature = "api-31")]
+fn binder_strong_interface() {
+    trait ILocal {}
 
-    let fd = open_memfd();
+    trait IOther {}
 
-    let binder = crate::service_manager::ServiceManager::get_service("activity")
-        .unwrap()
-        .unwrap();
+    struct LocalService;
 
-    binder.dump(fd.as_raw_fd(), &[]).unwrap();
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder";
 
-    let mut data: String = String::new();
-    std::process::Command::new("dumpsys")
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Err(Status::unknown_transaction())
+        }
+    }
+
+    struct OtherService;
+
+    impl Class for OtherService {
+        const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder.Other";
+
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Err(Status::unknown_transaction())
+        }
+    }
+
+    define_class!(LocalService);
+    define_class!(OtherService);
+    define_interface!(dyn ILocal, LocalService);
+    define_interface!(dyn IOther, OtherService);
+
+    let binder: IBinder = LocalService.into();
+
+    assert_eq!(binder.get_interface_descriptor().unwrap(), LocalService::INTERFACE_NAME);
+
+    let strong = Strong::<dyn ILocal>::new(binder.clone()).unwrap();
+
+    assert!(matches!(Strong::<dyn IOther>::new(binder), Err(st) if st.get_code() == Code::BadType));
+
+    let mut parcel = Parcel::new();
+
+    parcel.write(&Some(&strong)).unwrap();
+    parcel.write(&Some(&strong)).unwrap();
+    parcel.set_data_position(0).unwrap();
+
+    let r_strong: Option<Strong<dyn ILocal>> = parcel.read().unwrap();
+
+    assert!(r_strong.is_some());
+
+    let r_other = parcel.read::<Option<Strong<dyn IOther>>>();
+
+    assert!(matches!(r_other, Err(st) if st.get_code() == Code::BadType));
+}
+
+fn open_memfd() -> OwnedFd {
+    unsafe {
+        let func: unsafe extern "C" fn(name: *const std::ffi::c_char, flags: std::ffi::c_uint) -> RawFd =
+            std::mem::transmute(libc::dlsym(libc::RTLD_DEFAULT, "memfd_create\0".as_ptr().cast()));
+
+        OwnedFd::from_raw_fd(func("file\0".as_ptr().cast(), 0))
+    }
+}
+
+#[test]
+fn binder_dump() {
+    struct DumpService;
+
+    impl Class for DumpService {
+        const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder";
+
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Err(Status::unknown_transaction())
+        }
+
+        fn on_dump(&self, fd: RawFd, args: &[&str]) -> Result<(), Status> {
+            let args = CString::new(args.join(",")).unwrap().into_bytes();
+
+            unsafe {
+                libc::write(fd, args.as_ptr().cast(), args.len());
+            }
+
+            Ok(())
+        }
+    }
+
+    define_class!(DumpService);
+
+    let binder: IBinder = DumpService.into();
+
+    let fd = open_memfd();
+    let args: Vec<String> = Faker.fake();
+    let args = args.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+
+    binder.dump(fd.as_raw_fd(), &args).unwrap();
+
+    let content = std::fs::read_to_string(format!("/proc/self/fd/{}", fd.as_raw_fd())).unwrap();
+
+    assert_eq!(content, args.join(","));
+}
+
+#[cfg(all(feature = "service_manager", not(feature = "host-fake")))]
+#[test]
+fn binder_dump_sys_service() {
+    use std::io::Read;
+
+    let fd = open_memfd();
+
+    let binder = crate::service_manager::ServiceManager::get_service("activity")
+        .unwrap()
+        .unwrap();
+
+    binder.dump(fd.as_raw_fd(), &[]).unwrap();
+
+    let mut data: String = String::new();
+    std::process::Command::new("dumpsys")
         .arg("activity")
         .stdout(std::process::Stdio::piped())
         .spawn()
@@ -315,3 +926,861 @@ fn binder_dump_sys_service() {
     assert_eq!(&data[..5], &r_data[..5]);
     assert_eq!(&data[data.len() - 5..], &r_data[r_data.len() - 5..]);
 }
+
+#[test]
+#[cfg(all(feature = "host-fake", feature = "stability"))]
+fn binder_stability() {
+    struct VintfService;
+
+    impl Class for VintfService {
+        const INTERFACE_NAME: &'static str = "binder_stability";
+
+        fn stability() -> Option<Stability> {
+            Some(Stability::Vintf)
+        }
+
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Ok(())
+        }
+    }
+
+    define_class!(VintfService);
+
+    let binder: IBinder = VintfService.into();
+    assert_eq!(crate::sys::stability(binder.as_raw()), crate::sys::STABILITY_VINTF);
+
+    let binder = IBinder::try_new(VintfService).unwrap();
+    assert_eq!(crate::sys::stability(binder.as_raw()), crate::sys::STABILITY_VINTF);
+
+    binder.mark_stability(Stability::Vendor).unwrap();
+    assert_eq!(crate::sys::stability(binder.as_raw()), crate::sys::STABILITY_VENDOR);
+
+    let remote = unsafe { IBinder::from_raw(crate::sys::new_remote(binder.as_raw())) };
+    assert!(matches!(
+        remote.mark_stability(Stability::Local),
+        Err(StabilityError::RemoteBinder)
+    ));
+    assert_eq!(crate::sys::stability(remote.as_raw()), crate::sys::STABILITY_VENDOR);
+}
+
+#[test]
+#[cfg(all(feature = "host-fake", feature = "calling_sid"))]
+fn binder_calling_context() {
+    struct SidService;
+
+    impl Class for SidService {
+        const INTERFACE_NAME: &'static str = "binder_calling_context";
+
+        fn requesting_sid() -> bool {
+            true
+        }
+
+        fn on_transact(&self, _: u32, _: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            let context = CallingContext::current();
+            let reply = reply.unwrap();
+
+            reply.write(&context.pid)?;
+            reply.write(&context.uid)?;
+            reply.write(&context.sid)
+        }
+    }
+
+    define_class!(SidService);
+
+    let binder: IBinder = SidService.into();
+
+    let context = binder
+        .transact(
+            1,
+            |_| Ok(()),
+            |reply| {
+                let reply = reply.unwrap();
+
+                Ok(CallingContext {
+                    pid: reply.read()?,
+                    uid: reply.read()?,
+                    sid: reply.read()?,
+                })
+            },
+            Flags::empty(),
+        )
+        .unwrap();
+
+    assert_eq!(context.pid, IBinder::get_calling_pid());
+    assert_eq!(context.uid, IBinder::get_calling_uid());
+    assert_eq!(context.sid.as_deref(), crate::sys::CALLING_SID.to_str().ok());
+    assert_eq!(CallingContext::current().sid, None);
+
+    binder.set_requesting_sid(false).unwrap();
+
+    let sid = binder
+        .transact(
+            1,
+            |_| Ok(()),
+            |reply| {
+                let reply = reply.unwrap();
+                reply.read::<u32>()?;
+                reply.read::<u32>()?;
+                reply.read::<Option<String>>()
+            },
+            Flags::empty(),
+        )
+        .unwrap();
+
+    assert_eq!(sid, None);
+}
+
+#[test]
+#[cfg(all(feature = "host-fake", feature = "guard"))]
+fn binder_guard() {
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_guard";
+
+        fn enable_status_header() -> bool {
+            true
+        }
+
+        fn on_transact(&self, code: u32, _: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            reply.unwrap().write(&code)
+        }
+    }
+
+    struct RawService;
+
+    impl Class for RawService {
+        const INTERFACE_NAME: &'static str = "binder_guard_raw";
+
+        fn on_transact(&self, code: u32, _: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            reply.unwrap().write(&code)
+        }
+    }
+
+    define_class!(Guarded<LocalService>);
+    define_class!(Guarded<RawService>);
+
+    let uid = IBinder::get_calling_uid();
+    let guarded = Guarded::new(LocalService)
+        .with_default(Policy::Deny)
+        .with_policy(1, Policy::SameUid)
+        .with_policy(2, Policy::uids([uid]))
+        .with_policy(3, Policy::uids([uid.wrapping_add(1)]))
+        .with_policy(
+            4,
+            Policy::Any(std::vec![Policy::Pid(0), Policy::Pid(IBinder::get_calling_pid())]),
+        );
+    #[cfg(feature = "calling_sid")]
+    let guarded = guarded
+        .with_policy(5, Policy::All(std::vec![Policy::SameUid, Policy::sid("u:r:*:s0")]))
+        .with_policy(6, Policy::sid("u:r:untrusted_app:*"));
+    let binder: IBinder = guarded.into();
+
+    let call =
+        |code: u32| binder.transact_with_status_header(code, |_| Ok(()), |reply| reply.unwrap().read::<u32>(), Flags::empty());
+
+    let (allowed, denied) = if cfg!(feature = "calling_sid") {
+        (std::vec![1, 2, 4, 5], std::vec![3, 6, 7])
+    } else {
+        (std::vec![1, 2, 4], std::vec![3, 5, 6, 7])
+    };
+
+    for &code in &allowed {
+        assert_eq!(call(code).unwrap(), code);
+    }
+
+    for &code in &denied {
+        let err = call(code).unwrap_err();
+
+        assert_eq!(err.get_exception(), Exception::Security);
+        assert!(err.get_message().unwrap().unwrap().contains("binder_guard"));
+    }
+
+    let raw: IBinder = Guarded::new(RawService)
+        .with_default(Policy::Deny)
+        .with_policy(1, Policy::SameUid)
+        .into();
+
+    let call = |code: u32| raw.transact(code, |_| Ok(()), |reply| reply.unwrap().read::<u32>(), Flags::empty());
+
+    assert_eq!(call(1).unwrap(), 1);
+    assert_eq!(call(2).unwrap_err().get_code(), Code::PermissionDenied);
+
+    let context = CallingContext {
+        pid: 1,
+        uid: 2,
+        #[cfg(feature = "calling_sid")]
+        sid: Some("u:r:system_server:s0".to_string()),
+    };
+
+    #[cfg(feature = "calling_sid")]
+    {
+        assert!(Policy::sid("u:r:*:s0").check(&context).is_ok());
+        assert!(Policy::sid("u:r:system_server:s0").check(&context).is_ok());
+        assert!(Policy::sid("u:r:untrusted_app*").check(&context).is_err());
+        assert!(Policy::sid("u:*:*:*:*:s1").check(&context).is_err());
+        assert!(Policy::sid("*system*").check(&context).is_ok());
+        assert!(Policy::sid("u:r:system_server:s0*").check(&context).is_ok());
+        assert!(Policy::sid("*a*a*a*a*a*a*a*a*a*a*a*a*a*a*b")
+            .check(&CallingContext {
+                sid: Some("a".repeat(64)),
+                ..context.clone()
+            })
+            .is_err());
+    }
+    assert!(Policy::Any(std::vec![]).check(&context).is_err());
+}
+
+#[test]
+#[cfg(feature = "api-31")]
+fn binder_middleware() {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    };
+
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_middleware";
+
+        fn enable_status_header() -> bool {
+            true
+        }
+
+        fn on_transact(&self, _: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            let v: i32 = data.read()?;
+
+            reply.unwrap().write(&(v * 2))
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Middleware for Recorder {
+        fn before(&self, code: u32, data: &Parcel, context: &CallingContext) -> Result<(), Status> {
+            let v: i32 = data.read()?;
+
+            assert_eq!(context.uid, IBinder::get_calling_uid());
+
+            self.events.lock().unwrap().push(format!("before {} {}", code, v));
+
+            Ok(())
+        }
+
+        fn after(
+            &self,
+            code: u32,
+            data: &Parcel,
+            _: Option<&mut Parcel>,
+            _: &CallingContext,
+            _: std::time::Duration,
+            result: Result<(), Status>,
+        ) -> Result<(), Status> {
+            let v: i32 = data.read()?;
+
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("after {} {} {}", code, v, result.is_ok()));
+
+            result
+        }
+    }
+
+    struct FaultInjector(AtomicU32);
+
+    impl Middleware for FaultInjector {
+        fn before(&self, code: u32, _: &Parcel, _: &CallingContext) -> Result<(), Status> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+
+            if code == 2 {
+                Err(Status::with_exception_and_message(Exception::IllegalState, "injected").unwrap())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    define_class!(Layered<Layered<LocalService, Arc<Recorder>>, Arc<FaultInjector>>);
+
+    let recorder = Arc::new(Recorder::default());
+    let injector = Arc::new(FaultInjector(AtomicU32::new(0)));
+
+    let binder: IBinder = Layered::new(LocalService, recorder.clone()).layer(injector.clone()).into();
+
+    let v = binder
+        .transact_with_status_header(
+            1,
+            |data| data.write(&21i32),
+            |reply| reply.unwrap().read::<i32>(),
+            Flags::empty(),
+        )
+        .unwrap();
+
+    assert_eq!(v, 42);
+
+    let err = binder
+        .transact_with_status_header(2, |data| data.write(&1i32), |_| Ok(()), Flags::empty())
+        .unwrap_err();
+
+    assert_eq!(err.get_exception(), Exception::IllegalState);
+    assert_eq!(err.get_message().unwrap().as_deref(), Some("injected"));
+    assert_eq!(injector.0.load(Ordering::SeqCst), 2);
+    assert_eq!(*recorder.events.lock().unwrap(), ["before 1 21", "after 1 21 true"]);
+}
+
+#[test]
+fn binder_client_interceptor() {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    };
+
+    struct LocalService(i32);
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_client_interceptor";
+
+        fn on_transact(&self, _: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            let v: i32 = data.read()?;
+            let s: String = data.read()?;
+
+            let reply = reply.unwrap();
+            reply.write(&(v + self.0))?;
+            reply.write(&s)
+        }
+    }
+
+    define_class!(LocalService);
+
+    let primary: IBinder = LocalService(1).into();
+    let fallback: IBinder = LocalService(100).into();
+
+    let codes = Arc::new(Mutex::new(Vec::new()));
+    let faults = Arc::new(AtomicU32::new(1));
+
+    let client = BinderClient::new(primary.clone())
+        .with_interceptor({
+            let codes = codes.clone();
+            move |call: &Call<'_>| {
+                codes.lock().unwrap().push(call.code());
+                call.proceed()
+            }
+        })
+        .with_interceptor({
+            let fallback = fallback.clone();
+            move |call: &Call<'_>| match call.proceed() {
+                Err(err) if err.get_code() == Code::DeadObject => {
+                    call.rebind(fallback.clone());
+                    call.proceed()
+                }
+                result => result,
+            }
+        })
+        .with_interceptor({
+            let faults = faults.clone();
+            move |call: &Call<'_>| {
+                if faults
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |v| v.checked_sub(1))
+                    .is_ok()
+                {
+                    Err(Status::with_code(Code::DeadObject))
+                } else {
+                    call.proceed()
+                }
+            }
+        });
+
+    let call = |v: i32| {
+        client.transact(
+            3,
+            |data| {
+                data.write(&v)?;
+                data.write(&"text")
+            },
+            |reply| {
+                let reply = reply.unwrap();
+                Ok((reply.read::<i32>()?, reply.read::<String>()?))
+            },
+            Flags::empty(),
+        )
+    };
+
+    assert_eq!(call(1).unwrap(), (101, "text".to_string()));
+    assert_eq!(client.binder().as_raw(), fallback.as_raw());
+
+    faults.store(2, Ordering::SeqCst);
+
+    assert_eq!(call(2).unwrap_err().get_code(), Code::DeadObject);
+    assert_eq!(call(3).unwrap(), (103, "text".to_string()));
+    assert_eq!(*codes.lock().unwrap(), [3, 3, 3]);
+}
+
+#[test]
+fn binder_transact_timeout() {
+    use std::{thread, time::Duration};
+
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_transact_timeout";
+
+        fn on_transact(&self, _: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            let delay: u64 = data.read()?;
+
+            thread::sleep(Duration::from_millis(delay));
+
+            reply.unwrap().write(&delay)
+        }
+    }
+
+    define_class!(LocalService);
+
+    let binder: IBinder = LocalService.into();
+
+    let call = |delay: u64| {
+        binder.transact_with_timeout(
+            1,
+            |data| data.write(&delay),
+            |reply| reply.unwrap().read::<u64>(),
+            Flags::empty(),
+            Duration::from_millis(100),
+        )
+    };
+
+    assert_eq!(call(0).unwrap(), 0);
+    assert_eq!(call(500).unwrap_err().get_code(), Code::TimedOut);
+
+    let client = BinderClient::new(binder.clone()).with_timeout(Duration::from_millis(100));
+
+    let call = |delay: u64| {
+        client.transact(
+            1,
+            |data| data.write(&delay),
+            |reply| reply.unwrap().read::<u64>(),
+            Flags::empty(),
+        )
+    };
+
+    assert_eq!(call(10).unwrap(), 10);
+    assert_eq!(call(500).unwrap_err().get_code(), Code::TimedOut);
+
+    thread::sleep(Duration::from_millis(600));
+}
+
+#[test]
+#[cfg(all(feature = "host-fake", feature = "api-31", feature = "sensitive"))]
+fn binder_sensitive_transact() {
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_sensitive_transact";
+
+        fn on_transact(&self, _: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            let secret: String = data.read()?;
+
+            let reply = reply.unwrap();
+            reply.mark_sensitive()?;
+            reply.write(&(secret.len() as u32))?;
+            reply.write(&unsafe { crate::sys::is_sensitive(data.as_raw()) })
+        }
+    }
+
+    define_class!(LocalService);
+
+    let binder: IBinder = LocalService.into();
+
+    assert_eq!((Flags::ONEWAY | Flags::CLEAR_BUF).bits(), 0x21);
+    assert_eq!(Flags::PRIVATE_VENDOR.bits(), 0x10000000);
+
+    let (sensitive, len) = binder
+        .transact(
+            1,
+            |data| {
+                data.mark_sensitive()?;
+                data.write(&"secret")
+            },
+            |reply| {
+                let reply = reply.unwrap();
+
+                Ok((unsafe { crate::sys::is_sensitive(reply.as_raw()) }, reply.read::<u32>()?))
+            },
+            Flags::CLEAR_BUF,
+        )
+        .unwrap();
+
+    assert!(sensitive);
+    assert_eq!(len, 6);
+
+    assert_eq!(
+        binder.call(1).sensitive(true).arg(&"secret").send::<(u32, bool)>().unwrap(),
+        (6, true)
+    );
+    assert_eq!(binder.call(1).arg(&"secret").send::<(u32, bool)>().unwrap(), (6, false));
+
+    let parcel = Parcel::new();
+    assert!(!unsafe { crate::sys::is_sensitive(parcel.as_raw()) });
+
+    parcel.mark_sensitive().unwrap();
+    assert!(unsafe { crate::sys::is_sensitive(parcel.as_raw()) });
+}
+
+#[test]
+#[cfg(feature = "api-31")]
+fn binder_transaction_builder() {
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_transaction_builder";
+
+        fn on_transact(&self, code: u32, data: &Parcel, reply: Option<&mut Parcel>) -> Result<(), Status> {
+            let reply = reply.unwrap();
+
+            match code {
+                1 => {
+                    let (x, y, name): (i32, i32, Option<String>) = data.read()?;
+
+                    reply.write(&(x + y, name.map(|n| n.to_uppercase())))
+                }
+                2 => reply.write(&(1i32, 2i32)),
+                3 => Ok(()),
+                _ => Err(Status::unknown_transaction()),
+            }
+        }
+    }
+
+    define_class!(LocalService);
+
+    let binder: IBinder = LocalService.into();
+
+    for _ in 0..100 {
+        let value: (i64, Option<String>, bool, Vec<u8>) = Faker.fake();
+
+        let mut parcel = Parcel::new();
+        parcel.write(&value).unwrap();
+        parcel.set_data_position(0).unwrap();
+
+        assert_eq!(parcel.read::<(i64, Option<String>, bool, Vec<u8>)>().unwrap(), value);
+    }
+
+    let reply = binder
+        .call(1)
+        .arg(&20i32)
+        .arg(&22i32)
+        .arg(&Some("name"))
+        .oneway(false)
+        .send::<(i32, Option<String>)>()
+        .unwrap();
+
+    assert_eq!(reply, (42, Some("NAME".to_string())));
+
+    assert_eq!(binder.call(2).send::<(i32, i32)>().unwrap(), (1, 2));
+    assert_eq!(binder.call(2).send::<(i32,)>().unwrap_err().get_code(), Code::BadValue);
+    assert_eq!(binder.call(2).run().unwrap_err().get_code(), Code::BadValue);
+    assert_eq!(
+        binder.call(1).arg(&1i32).send::<(i32,)>().unwrap_err().get_code(),
+        Code::NotEnoughData
+    );
+
+    binder.call(3).run().unwrap();
+    binder.call(3).send::<()>().unwrap();
+    binder.call(2).oneway(true).run().unwrap();
+    assert_eq!(
+        binder.call(2).oneway(true).send::<()>().unwrap_err().get_code(),
+        Code::InvalidOperation
+    );
+}
+
+#[test]
+#[cfg(feature = "process")]
+fn process_thread_pool() {
+    assert!(BinderProcess::set_thread_pool_max_thread_count(4).unwrap());
+
+    BinderProcess::start_thread_pool().unwrap();
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "process"))]
+fn process_polling() {
+    let fd = BinderProcess::setup_polling().unwrap();
+
+    assert!(fd >= 0);
+}
+
+#[test]
+#[cfg(all(feature = "api-31", feature = "async"))]
+fn binder_async_transact() {
+    struct AsyncLocalService;
+
+    impl AsyncClass for AsyncLocalService {
+        const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder";
+
+        fn enable_status_header() -> bool {
+            true
+        }
+
+        fn on_transact_async<'a>(
+            &'a self,
+            code: u32,
+            data: &'a Parcel,
+            reply: Option<&'a mut Parcel>,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), Status>> + 'a>> {
+            Box::pin(async move {
+                match code {
+                    1 => {
+                        let v: u64 = data.read()?;
+
+                        reply.unwrap().write(&(v + 114514))
+                    }
+                    _ => Err(Status::unknown_transaction()),
+                }
+            })
+        }
+
+        fn on_dump(&self, fd: RawFd, _: &[&str]) -> Result<(), Status> {
+            unsafe { libc::write(fd, b"async".as_ptr().cast(), 5) };
+
+            Ok(())
+        }
+    }
+
+    define_class!(AsyncService<AsyncLocalService, BlockingRuntime>);
+
+    let binder: IBinder = AsyncService::new(AsyncLocalService, BlockingRuntime).into();
+
+    let v: u64 = Faker.fake::<u32>() as u64;
+
+    let rvs = (0..64)
+        .map(|_| {
+            binder.transact_async(
+                1,
+                move |data| data.write(&v),
+                |reply| {
+                    let reply = reply.unwrap();
+                    reply.read::<Status>()?.err(|| ())?;
+                    reply.read::<u64>()
+                },
+                Flags::empty(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    for rv in rvs {
+        assert_eq!(v + 114514, BlockingRuntime.block_on(rv).unwrap());
+    }
+
+    let rn = BlockingRuntime.block_on(binder.transact_async(
+        2,
+        |_| Ok(()),
+        |reply| reply.unwrap().read::<Status>()?.err(|| ()),
+        Flags::empty(),
+    ));
+
+    assert!(matches!(rn, Err(st) if st.get_code() == Code::UnknownTransaction));
+
+    let fd = open_memfd();
+    binder.dump(fd.as_raw_fd(), &[]).unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(format!("/proc/self/fd/{}", fd.as_raw_fd())).unwrap(),
+        "async"
+    );
+}
+
+#[test]
+#[cfg(feature = "host-fake")]
+fn binder_death_recipient() {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "com.github.kr328.NdkBinder";
+
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Ok(())
+        }
+    }
+
+    struct Recipient(Arc<AtomicBool>);
+
+    impl DeathRecipient for Recipient {
+        fn on_dead(self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    define_class!(LocalService);
+
+    let local: IBinder = LocalService.into();
+    let remote = Arc::new(unsafe { IBinder::from_raw(crate::sys::new_remote(local.as_raw())) });
+
+    assert!(remote.is_remote());
+    assert!(remote.ping().is_ok());
+
+    let dead = Arc::new(AtomicBool::new(false));
+    let linked = IBinder::link_to_death(remote.clone(), Recipient(dead.clone())).unwrap();
+
+    crate::sys::kill_remote(remote.as_raw());
+
+    assert!(dead.load(Ordering::SeqCst));
+    assert!(!remote.is_alive());
+    assert!(matches!(remote.ping(), Err(st) if st.get_code() == Code::DeadObject));
+
+    drop(linked);
+}
+
+#[test]
+#[cfg(all(feature = "host-fake", feature = "api-33"))]
+fn binder_death_unlinked() {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_death_unlinked";
+
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Ok(())
+        }
+    }
+
+    struct Recipient(Arc<AtomicU32>, Arc<AtomicU32>);
+
+    impl DeathRecipient for Recipient {
+        fn on_dead(self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_unlinked(self) {
+            self.1.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    define_class!(LocalService);
+
+    let local: IBinder = LocalService.into();
+    let remote = unsafe { IBinder::from_raw(crate::sys::new_remote(local.as_raw())) };
+
+    let dead = Arc::new(AtomicU32::new(0));
+    let unlinked = Arc::new(AtomicU32::new(0));
+    let recipient = || Recipient(dead.clone(), unlinked.clone());
+
+    let linked = IBinder::link_to_death(remote.clone(), recipient()).unwrap();
+    drop(linked);
+
+    assert_eq!(dead.load(Ordering::SeqCst), 0);
+    assert_eq!(unlinked.load(Ordering::SeqCst), 1);
+
+    let err = IBinder::link_to_death(&local, recipient()).err().unwrap();
+
+    assert_eq!(err.get_code(), Code::InvalidOperation);
+    assert_eq!(unlinked.load(Ordering::SeqCst), 2);
+
+    let linked = IBinder::link_to_death(&remote, recipient()).unwrap();
+
+    crate::sys::kill_remote(remote.as_raw());
+    drop(linked);
+
+    assert_eq!(dead.load(Ordering::SeqCst), 1);
+    assert_eq!(unlinked.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+#[cfg(feature = "host-fake")]
+fn binder_death_watcher() {
+    use std::{
+        future::Future,
+        pin::pin,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        task::{Context, Poll, Waker},
+    };
+
+    struct LocalService;
+
+    impl Class for LocalService {
+        const INTERFACE_NAME: &'static str = "binder_death_watcher";
+
+        fn on_transact(&self, _: u32, _: &Parcel, _: Option<&mut Parcel>) -> Result<(), Status> {
+            Ok(())
+        }
+    }
+
+    define_class!(LocalService);
+
+    let local: IBinder = LocalService.into();
+    let remote = unsafe { IBinder::from_raw(crate::sys::new_remote(local.as_raw())) };
+
+    let watcher = DeathWatcher::new(remote.clone()).unwrap();
+    let count = Arc::new(AtomicU32::new(0));
+
+    for _ in 0..3 {
+        let count = count.clone();
+        watcher.subscribe(move || {
+            count.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    let removed = watcher
+        .subscribe({
+            let count = count.clone();
+            move || {
+                count.fetch_add(100, Ordering::SeqCst);
+            }
+        })
+        .unwrap();
+    assert!(watcher.unsubscribe(removed));
+
+    let channel = watcher.channel();
+    let mut dead = pin!(watcher.dead());
+    let mut cx = Context::from_waker(Waker::noop());
+
+    assert!(dead.as_mut().poll(&mut cx).is_pending());
+    assert!(!watcher.is_dead());
+    assert_eq!(watcher.binder().as_raw(), remote.as_raw());
+
+    crate::sys::kill_remote(remote.as_raw());
+
+    assert!(watcher.is_dead());
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+    assert!(channel.try_recv().is_ok());
+    assert!(matches!(dead.as_mut().poll(&mut cx), Poll::Ready(Ok(()))));
+
+    assert!(watcher
+        .subscribe({
+            let count = count.clone();
+            move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .is_none());
+    assert_eq!(count.load(Ordering::SeqCst), 4);
+
+    let remote = unsafe { IBinder::from_raw(crate::sys::new_remote(local.as_raw())) };
+    let watcher = DeathWatcher::new(remote.clone()).unwrap();
+    let channel = watcher.channel();
+    let mut dead = pin!(watcher.dead());
+
+    assert!(dead.as_mut().poll(&mut cx).is_pending());
+
+    drop(watcher);
+
+    assert!(channel.recv().is_err());
+    assert_eq!(
+        dead.as_mut().poll(&mut cx).map(|r| r.unwrap_err().get_code()),
+        Poll::Ready(Code::InvalidOperation)
+    );
+}
@@ -0,0 +1,110 @@
+use crate::{Code, Flags, IBinder, Parcel, Read, Status, Write};
+
+type Arg<'a> = Box<dyn FnOnce(&mut Parcel) -> Result<(), Status> + 'a>;
+
+/// Only available with `api-31`, unread reply data is detected with `AParcel_getDataSize`.
+pub struct TransactionBuilder<'a> {
+    binder: &'a IBinder,
+    code: u32,
+    args: Vec<Arg<'a>>,
+    flags: Flags,
+    status_header: bool,
+    #[cfg(feature = "sensitive")]
+    sensitive: bool,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    pub fn arg<T: Write>(mut self, value: &'a T) -> Self {
+        self.args.push(Box::new(move |parcel| parcel.write(value)));
+        self
+    }
+
+    pub fn oneway(mut self, oneway: bool) -> Self {
+        self.flags.set(Flags::ONEWAY, oneway);
+        self
+    }
+
+    pub fn flags(mut self, flags: Flags) -> Self {
+        self.flags |= flags;
+        self
+    }
+
+    pub fn status_header(mut self, status_header: bool) -> Self {
+        self.status_header = status_header;
+        self
+    }
+
+    /// Marks the transaction data sensitive before the arguments are written.
+    #[cfg(feature = "sensitive")]
+    pub fn sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    fn transact(self) -> Result<Option<Parcel>, Status> {
+        #[cfg(feature = "sensitive")]
+        let sensitive = self.sensitive;
+        let args = self.args;
+
+        let reply = self.binder.transact_raw(
+            self.code,
+            |parcel| {
+                #[cfg(feature = "sensitive")]
+                if sensitive {
+                    parcel.mark_sensitive()?;
+                }
+
+                args.into_iter().try_for_each(|arg| arg(parcel))
+            },
+            self.flags,
+        )?;
+
+        match reply {
+            Some(reply) if self.status_header && !self.flags.contains(Flags::ONEWAY) => {
+                reply.read::<Status>()?.err(|| ())?;
+
+                Ok(Some(reply))
+            }
+            reply => Ok(reply),
+        }
+    }
+
+    /// Fails with `InvalidOperation` without sending for oneway transactions, which have no reply, use [`run`](Self::run).
+    pub fn send<R: Read>(self) -> Result<R, Status> {
+        if self.flags.contains(Flags::ONEWAY) {
+            return Err(Status::with_code(Code::InvalidOperation));
+        }
+
+        let reply = self.transact()?.ok_or_else(|| Status::with_code(Code::UnexpectedNull))?;
+
+        let value = reply.read::<R>()?;
+        if reply.get_data_position() != reply.get_data_size() {
+            return Err(Status::bad_value());
+        }
+
+        Ok(value)
+    }
+
+    pub fn run(self) -> Result<(), Status> {
+        let oneway = self.flags.contains(Flags::ONEWAY);
+
+        match self.transact()? {
+            Some(reply) if !oneway && reply.get_data_position() != reply.get_data_size() => Err(Status::bad_value()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IBinder {
+    pub fn call(&self, code: u32) -> TransactionBuilder<'_> {
+        TransactionBuilder {
+            binder: self,
+            code,
+            args: Vec::new(),
+            flags: Flags::empty(),
+            status_header: false,
+            #[cfg(feature = "sensitive")]
+            sensitive: false,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "api-31")]
+
+use ndkbinder::{Code, Flags, Parcel, Status, Strong};
+
+#[allow(dead_code)]
+mod generated {
+    include!("aidl/generated.rs");
+}
+
+use generated::com::example::{
+    types::{Color, Point, Value},
+    IRemote, IRemoteProxy, IRemoteStub,
+};
+
+struct Remote;
+
+impl IRemote for Remote {
+    fn add(&self, a: i32, b: i32) -> Result<i32, Status> {
+        Ok(a + b)
+    }
+
+    fn echo(&self, value: Option<&str>) -> Result<Option<String>, Status> {
+        Ok(value.map(|v| v.repeat(2)))
+    }
+
+    fn fill(&self, values: &mut Vec<i32>, point: &mut Point) -> Result<(), Status> {
+        for (idx, value) in values.iter_mut().enumerate() {
+            *value = idx as i32 * 10;
+        }
+
+        point.x += 100;
+        point.label = Some("filled".into());
+
+        Ok(())
+    }
+
+    fn notify(&self, _callback: &ndkbinder::IBinder) -> Result<(), Status> {
+        Ok(())
+    }
+}
+
+#[test]
+fn aidl_generated() {
+    let proxy = IRemoteProxy::new(Strong::new(IRemoteStub::new_binder(Remote)).unwrap());
+
+    assert_eq!(proxy.add(2, 3).unwrap(), 5);
+    assert_eq!(proxy.echo(Some("ab")).unwrap().as_deref(), Some("abab"));
+    assert_eq!(proxy.echo(None).unwrap(), None);
+
+    let mut values = vec![0; 4];
+    let mut point = Point::default();
+    proxy.fill(&mut values, &mut point).unwrap();
+
+    assert_eq!(values, vec![0, 10, 20, 30]);
+    assert_eq!(point.x, 101);
+    assert_eq!(point.label.as_deref(), Some("filled"));
+
+    proxy.notify(proxy.as_binder()).unwrap();
+
+    let mut parcel = Parcel::new();
+    parcel.write(&Some(Value::Color(Color::BLUE))).unwrap();
+    parcel.write::<Option<Point>>(&None).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert!(matches!(parcel.read::<Value>().unwrap(), Value::Color(Color::BLUE)));
+    assert!(parcel.read::<Option<Point>>().unwrap().is_none());
+}
+
+#[test]
+fn aidl_out_array_len() {
+    let binder = IRemoteStub::new_binder(Remote);
+
+    let result = binder.transact_with_status_header(
+        IRemoteStub::TRANSACTION_FILL,
+        |data| {
+            data.write(&i32::MAX)?;
+            data.write(&Point::default())
+        },
+        |_| Ok(()),
+        Flags::empty(),
+    );
+
+    assert!(matches!(result, Err(s) if s.get_code() == Code::NoMemory));
+}
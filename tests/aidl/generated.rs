@@ -0,0 +1,313 @@
+pub mod com {
+#![allow(non_upper_case_globals, non_snake_case, clippy::all, unused_imports, unused_mut, unused_variables)]
+pub mod example {
+pub trait IRemote: Send + Sync {
+fn add(&self, a: i32, b: i32) -> Result<i32, ::ndkbinder::Status>;
+fn echo(&self, value: Option<&str>) -> Result<Option<String>, ::ndkbinder::Status>;
+fn fill(&self, values: &mut Vec<i32>, point: &mut super::super::com::example::types::Point) -> Result<(), ::ndkbinder::Status>;
+fn notify(&self, callback: &::ndkbinder::IBinder) -> Result<(), ::ndkbinder::Status>;
+}
+impl dyn IRemote {
+pub const VERSION: i32 = 3i32;
+pub const NAME: &'static str = "remote";
+}
+pub struct IRemoteStub(Box<dyn IRemote>);
+impl IRemoteStub {
+pub const TRANSACTION_ADD: u32 = ::ndkbinder::FIRST_CALL_TRANSACTION + 0;
+pub const TRANSACTION_ECHO: u32 = ::ndkbinder::FIRST_CALL_TRANSACTION + 1;
+pub const TRANSACTION_FILL: u32 = ::ndkbinder::FIRST_CALL_TRANSACTION + 2;
+pub const TRANSACTION_NOTIFY: u32 = ::ndkbinder::FIRST_CALL_TRANSACTION + 3;
+pub fn new_binder<T: IRemote + 'static>(service: T) -> ::ndkbinder::IBinder { IRemoteStub(Box::new(service)).into() }
+}
+impl ::ndkbinder::Class for IRemoteStub {
+    const INTERFACE_NAME: &'static str = "com.example.IRemote";
+
+    fn enable_status_header() -> bool {
+        true
+    }
+
+    fn on_transact(&self, code: u32, data: &::ndkbinder::Parcel, reply: Option<&mut ::ndkbinder::Parcel>) -> Result<(), ::ndkbinder::Status> {
+        match code {
+            IRemoteStub::TRANSACTION_ADD => {
+let _arg_a = data.read::<i32>()?;
+let _arg_b = data.read::<i32>()?;
+let _aidl_reply = reply.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))?;
+let _aidl_return = self.0.add(_arg_a, _arg_b)?;
+_aidl_reply.write(&_aidl_return)?;
+Ok(())
+}
+IRemoteStub::TRANSACTION_ECHO => {
+let _arg_value = data.read::<Option<String>>()?;
+let _aidl_reply = reply.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))?;
+let _aidl_return = self.0.echo(_arg_value.as_deref())?;
+_aidl_reply.write(&_aidl_return.as_deref())?;
+Ok(())
+}
+IRemoteStub::TRANSACTION_FILL => {
+let mut _arg_values: Vec<i32> = (0..data.read_out_array_len()?.unwrap_or(0)).map(|_| Default::default()).collect();
+let mut _arg_point = data.read::<super::super::com::example::types::Point>()?;
+let _aidl_reply = reply.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))?;
+let _aidl_return = self.0.fill(&mut _arg_values, &mut _arg_point)?;
+_aidl_reply.write(&_arg_values.as_slice())?;
+_aidl_reply.write(&_arg_point)?;
+Ok(())
+}
+IRemoteStub::TRANSACTION_NOTIFY => {
+let _arg_callback = data.read::<::ndkbinder::IBinder>()?;
+let _ = self.0.notify(&_arg_callback);
+Ok(())
+}
+
+            _ => Err(::ndkbinder::Status::unknown_transaction()),
+        }
+    }
+}
+
+::ndkbinder::define_class!(IRemoteStub);
+
+::ndkbinder::define_interface!(dyn IRemote, IRemoteStub);
+
+pub struct IRemoteProxy {
+    binder: ::ndkbinder::Strong<dyn IRemote>,
+}
+
+impl IRemoteProxy {
+    pub fn new(binder: ::ndkbinder::Strong<dyn IRemote>) -> Self {
+        IRemoteProxy { binder }
+    }
+
+    pub fn as_binder(&self) -> &::ndkbinder::IBinder {
+        self.binder.as_binder()
+    }
+}
+impl IRemote for IRemoteProxy {
+fn add(&self, a: i32, b: i32) -> Result<i32, ::ndkbinder::Status> {
+                let (_aidl_return, ) = self.binder.as_binder().transact_with_status_header(
+                    IRemoteStub::TRANSACTION_ADD,
+                    |data| {
+                        data.write(&a)?;
+data.write(&b)?;
+
+                        Ok(())
+                    },
+                    |reply| {
+                        let reply = reply.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))?;
+                        let _aidl_return = reply.read::<i32>()?;
+                        
+                        Ok((_aidl_return, ))
+                    },
+                    ::ndkbinder::Flags::empty(),
+                )?;
+                
+                Ok(_aidl_return)
+            }
+fn echo(&self, value: Option<&str>) -> Result<Option<String>, ::ndkbinder::Status> {
+                let (_aidl_return, ) = self.binder.as_binder().transact_with_status_header(
+                    IRemoteStub::TRANSACTION_ECHO,
+                    |data| {
+                        data.write(&value)?;
+
+                        Ok(())
+                    },
+                    |reply| {
+                        let reply = reply.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))?;
+                        let _aidl_return = reply.read::<Option<String>>()?;
+                        
+                        Ok((_aidl_return, ))
+                    },
+                    ::ndkbinder::Flags::empty(),
+                )?;
+                
+                Ok(_aidl_return)
+            }
+fn fill(&self, values: &mut Vec<i32>, point: &mut super::super::com::example::types::Point) -> Result<(), ::ndkbinder::Status> {
+                let (_aidl_return, _out_values, _out_point, ) = self.binder.as_binder().transact_with_status_header(
+                    IRemoteStub::TRANSACTION_FILL,
+                    |data| {
+                        data.write(&(values.len() as i32))?;
+data.write(&(*point))?;
+
+                        Ok(())
+                    },
+                    |reply| {
+                        let reply = reply.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))?;
+                        let _aidl_return = ();
+                        let _out_values = reply.read::<Vec<i32>>()?;
+let _out_point = reply.read::<super::super::com::example::types::Point>()?;
+
+                        Ok((_aidl_return, _out_values, _out_point, ))
+                    },
+                    ::ndkbinder::Flags::empty(),
+                )?;
+                *values = _out_values;
+*point = _out_point;
+
+                Ok(_aidl_return)
+            }
+fn notify(&self, callback: &::ndkbinder::IBinder) -> Result<(), ::ndkbinder::Status> {
+                let () = self.binder.as_binder().transact_with_status_header(
+                    IRemoteStub::TRANSACTION_NOTIFY,
+                    |data| {
+                        data.write(&callback)?;
+
+                        Ok(())
+                    },
+                    |_| Ok(()),
+                    ::ndkbinder::Flags::ONEWAY,
+                )?;
+                
+                Ok(())
+            }
+}
+pub mod types {
+#[derive(Debug)]
+pub struct Point {
+pub x: i32,
+pub y: i32,
+pub label: Option<String>,
+pub children: Vec<Point>,
+}
+impl Point {
+}
+impl Default for Point {
+fn default() -> Self {
+Self {
+x: 1i32,
+y: Default::default(),
+label: Default::default(),
+children: Default::default(),
+}
+}
+}
+impl ::ndkbinder::ParcelableMetadata for Point {
+    const DESCRIPTOR: &'static str = "com.example.types.Point";
+}
+
+impl ::ndkbinder::Read for Point {
+    fn read(parcel: &::ndkbinder::Parcel) -> Result<Self, ::ndkbinder::Status> {
+        <Self as ::ndkbinder::ReadNullable>::read_nullable(parcel)?.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))
+    }
+}
+
+impl ::ndkbinder::WriteNullable for Point {}
+impl ::ndkbinder::ReadArray for Point {}
+impl ::ndkbinder::WriteArray for Point {}
+impl ::ndkbinder::ReadNullable for Point {
+    fn read_nullable(parcel: &::ndkbinder::Parcel) -> Result<Option<Self>, ::ndkbinder::Status> {
+        if parcel.read::<i32>()? == 0 {
+            return Ok(None);
+        }
+
+        parcel.read_sized(|reader| {
+            let mut value = Point::default();
+            if reader.has_more_data() { value.x = parcel.read::<i32>()?; }
+if reader.has_more_data() { value.y = parcel.read::<i32>()?; }
+if reader.has_more_data() { value.label = parcel.read::<Option<String>>()?; }
+if reader.has_more_data() { value.children = parcel.read::<Vec<Point>>()?; }
+
+            Ok(Some(value))
+        })
+    }
+}
+
+impl ::ndkbinder::Write for Point {
+    fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> Result<(), ::ndkbinder::Status> {
+        parcel.write(&1i32)?;
+        parcel.write_sized(|parcel| {
+            parcel.write(&self.x)?;
+parcel.write(&self.y)?;
+parcel.write(&self.label.as_deref())?;
+parcel.write(&self.children.as_slice())?;
+
+            Ok(())
+        })
+    }
+}
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Color(pub i32);
+impl Color {
+pub const RED: Color = Color(1i32);
+pub const GREEN: Color = Color(Self::RED.0 + 1);
+pub const BLUE: Color = Color(1i32<<4i32);
+}
+impl ::ndkbinder::Read for Color {
+    fn read(parcel: &::ndkbinder::Parcel) -> Result<Self, ::ndkbinder::Status> {
+        Ok(Color(parcel.read()?))
+    }
+}
+
+impl ::ndkbinder::Write for Color {
+    fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> Result<(), ::ndkbinder::Status> {
+        parcel.write(&self.0)
+    }
+}
+
+impl ::ndkbinder::ReadArray for Color {
+    fn read_array(parcel: &::ndkbinder::Parcel) -> Result<Option<Vec<Self>>, ::ndkbinder::Status> {
+        Ok(parcel.read::<Option<Vec<i32>>>()?.map(|v| v.into_iter().map(Color).collect()))
+    }
+}
+
+impl ::ndkbinder::WriteArray for Color {
+    fn write_array(parcel: &mut ::ndkbinder::Parcel, values: Option<&[Self]>) -> Result<(), ::ndkbinder::Status> {
+        parcel.write(&values.map(|v| v.iter().map(|e| e.0).collect::<Vec<_>>()))
+    }
+}
+#[derive(Debug)]
+pub enum Value {
+Number(i32),
+Text(String),
+Color(Color),
+}
+impl Value {
+pub const TAG_NUMBER: i32 = 0;
+pub const TAG_TEXT: i32 = 1;
+pub const TAG_COLOR: i32 = 2;
+}
+impl Default for Value { fn default() -> Self { Self::Number(Default::default()) } }
+impl ::ndkbinder::ParcelableMetadata for Value {
+    const DESCRIPTOR: &'static str = "com.example.types.Value";
+}
+
+impl ::ndkbinder::Read for Value {
+    fn read(parcel: &::ndkbinder::Parcel) -> Result<Self, ::ndkbinder::Status> {
+        <Self as ::ndkbinder::ReadNullable>::read_nullable(parcel)?.ok_or_else(|| ::ndkbinder::Status::with_code(::ndkbinder::Code::UnexpectedNull))
+    }
+}
+
+impl ::ndkbinder::WriteNullable for Value {}
+impl ::ndkbinder::ReadArray for Value {}
+impl ::ndkbinder::WriteArray for Value {}
+impl ::ndkbinder::ReadNullable for Value {
+    fn read_nullable(parcel: &::ndkbinder::Parcel) -> Result<Option<Self>, ::ndkbinder::Status> {
+        if parcel.read::<i32>()? == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(match parcel.read::<i32>()? {
+            0 => Value::Number(parcel.read::<i32>()?),
+1 => Value::Text(parcel.read::<String>()?),
+2 => Value::Color(parcel.read::<Color>()?),
+
+            _ => return Err(::ndkbinder::Status::bad_value()),
+        }))
+    }
+}
+
+impl ::ndkbinder::Write for Value {
+    fn write(&self, parcel: &mut ::ndkbinder::Parcel) -> Result<(), ::ndkbinder::Status> {
+        parcel.write(&1i32)?;
+
+        match self {
+            Self::Number(v) => { parcel.write(&0i32)?; parcel.write(&(*v))?; }
+Self::Text(v) => { parcel.write(&1i32)?; parcel.write(&(*v).as_str())?; }
+Self::Color(v) => { parcel.write(&2i32)?; parcel.write(&(*v))?; }
+
+        }
+
+        Ok(())
+    }
+}
+}
+}
+}
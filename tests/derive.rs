@@ -0,0 +1,59 @@
+#![cfg(all(feature = "api-31", feature = "derive"))]
+
+use ndkbinder::{Code, Parcel, Read, Write};
+
+#[derive(Debug, Default, PartialEq, Read, Write)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Default, PartialEq, Read, Write)]
+struct Shape(Option<String>, Vec<Point>, Option<Point>);
+
+#[derive(Debug, PartialEq, Read, Write)]
+enum Value {
+    Number(i32),
+    Point(Option<Point>),
+}
+
+#[test]
+fn derive_struct() {
+    let shape = Shape(
+        Some("triangle".into()),
+        vec![Point { x: 0, y: 0 }, Point { x: 1, y: 1 }],
+        None,
+    );
+    let points = vec![Some(Point { x: 2, y: 3 }), None];
+
+    let mut parcel = Parcel::new();
+    parcel.write(&shape).unwrap();
+    parcel.write(&Some(Point { x: 4, y: 5 })).unwrap();
+    parcel.write::<Option<Point>>(&None).unwrap();
+    parcel.write(&points).unwrap();
+    parcel.write::<Option<Shape>>(&None).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<Shape>().unwrap(), shape);
+    assert_eq!(parcel.read::<Option<Point>>().unwrap(), Some(Point { x: 4, y: 5 }));
+    assert_eq!(parcel.read::<Option<Point>>().unwrap(), None);
+    assert_eq!(parcel.read::<Vec<Option<Point>>>().unwrap(), points);
+    assert!(matches!(parcel.read::<Shape>(), Err(s) if s.get_code() == Code::UnexpectedNull));
+}
+
+#[test]
+fn derive_union() {
+    let values = vec![Some(Value::Point(None)), None, Some(Value::Number(7))];
+
+    let mut parcel = Parcel::new();
+    parcel.write(&Value::Point(Some(Point { x: 1, y: 2 }))).unwrap();
+    parcel.write::<Option<Value>>(&None).unwrap();
+    parcel.write(&values).unwrap();
+
+    parcel.set_data_position(0).unwrap();
+
+    assert_eq!(parcel.read::<Value>().unwrap(), Value::Point(Some(Point { x: 1, y: 2 })));
+    assert_eq!(parcel.read::<Option<Value>>().unwrap(), None);
+    assert_eq!(parcel.read::<Vec<Option<Value>>>().unwrap(), values);
+}